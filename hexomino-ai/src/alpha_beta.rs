@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use hexomino_core::{Action, GamePhase, Seat, State};
+
+use super::{
+    eval::{Evaluator, WIN_SCORE},
+    limits::{Budget, SearchLimits},
+    Searcher,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    /// The score is at least the stored one.
+    Lower,
+    /// The score is at most the stored one.
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    depth: usize,
+    score: i32,
+    bound: Bound,
+    best_action: Option<Action>,
+}
+
+/// A minimax searcher with alpha-beta pruning, iterative deepening and a transposition table.
+///
+/// With more than two players, it assumes all the other players play against the player to
+/// move, i.e. it searches the paranoid tree.
+pub struct AlphaBeta {
+    evaluator: Evaluator,
+    max_depth: usize,
+    limits: SearchLimits,
+    table: HashMap<u128, Entry>,
+}
+
+impl AlphaBeta {
+    /// Creates a searcher that looks at most `max_depth` actions ahead, within the limits.
+    pub fn new(evaluator: Evaluator, max_depth: usize, limits: SearchLimits) -> Self {
+        Self {
+            evaluator,
+            max_depth,
+            limits,
+            table: HashMap::new(),
+        }
+    }
+
+    /// Returns the best action for the current player with its score, found by the deepest
+    /// search that is completed within the limits.
+    ///
+    /// If not even a search of depth 1 is completed, the first legal action is returned with
+    /// the score of the state.
+    pub fn search_with_score(&mut self, state: &State) -> Option<(Action, i32)> {
+        let root = state.current_seat()?;
+        let first_action = state.legal_actions().next()?;
+        let mut best = (first_action, self.evaluator.evaluate(state, root));
+        let mut budget = Budget::new(self.limits);
+        let mut state = state.clone();
+        self.table.clear();
+        for depth in 1..=self.max_depth {
+            let score =
+                match self.alpha_beta(&mut state, root, depth, i32::MIN, i32::MAX, &mut budget) {
+                    Some(score) => score,
+                    None => break,
+                };
+            let best_action = self
+                .table
+                .get(&state.zobrist_hash128())
+                .and_then(|entry| entry.best_action);
+            best = (best_action.unwrap_or(first_action), score);
+            if score.abs() >= WIN_SCORE {
+                // The result is proven, and a deeper search cannot change it.
+                break;
+            }
+        }
+        Some(best)
+    }
+
+    /// Returns the score of the state for the root player, or `None` if the budget runs out.
+    fn alpha_beta(
+        &mut self,
+        state: &mut State,
+        root: Seat,
+        depth: usize,
+        mut alpha: i32,
+        mut beta: i32,
+        budget: &mut Budget,
+    ) -> Option<i32> {
+        if budget.tick() {
+            return None;
+        }
+        if depth == 0 || state.phase() == GamePhase::End {
+            let score = self.evaluator.evaluate(state, root);
+            // Prefer quicker wins and slower losses.
+            return Some(match score {
+                WIN_SCORE => score + depth as i32,
+                _ if score == -WIN_SCORE => score - depth as i32,
+                _ => score,
+            });
+        }
+        let key = state.zobrist_hash128();
+        let (alpha_before, beta_before) = (alpha, beta);
+        let mut tt_action = None;
+        if let Some(entry) = self.table.get(&key) {
+            tt_action = entry.best_action;
+            if entry.depth >= depth {
+                match entry.bound {
+                    Bound::Exact => return Some(entry.score),
+                    Bound::Lower => alpha = alpha.max(entry.score),
+                    Bound::Upper => beta = beta.min(entry.score),
+                }
+                if alpha >= beta {
+                    return Some(entry.score);
+                }
+            }
+        }
+
+        let maximizing = state.current_seat() == Some(root);
+        let mut actions: Vec<_> = state.legal_actions().collect();
+        if let Some(index) =
+            tt_action.and_then(|tt_action| actions.iter().position(|&action| action == tt_action))
+        {
+            actions.swap(0, index);
+        }
+        let mut best_score = if maximizing { i32::MIN } else { i32::MAX };
+        let mut best_action = None;
+        for action in actions {
+            state.current_player_play(action).unwrap();
+            let score = self.alpha_beta(state, root, depth - 1, alpha, beta, budget);
+            state.undo();
+            let score = score?;
+            if (maximizing && score > best_score) || (!maximizing && score < best_score) {
+                best_score = score;
+                best_action = Some(action);
+            }
+            if maximizing {
+                alpha = alpha.max(score);
+            } else {
+                beta = beta.min(score);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= alpha_before {
+            Bound::Upper
+        } else if best_score >= beta_before {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table.insert(
+            key,
+            Entry {
+                depth,
+                score: best_score,
+                bound,
+                best_action,
+            },
+        );
+        Some(best_score)
+    }
+}
+
+impl Searcher for AlphaBeta {
+    fn search(&mut self, state: &State) -> Option<Action> {
+        self.search_with_score(state).map(|(action, _)| action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::testing::{domino_endgame, is_middle_column};
+
+    #[test]
+    fn finds_the_only_winning_move() {
+        let state = domino_endgame();
+        let mut searcher = AlphaBeta::new(Evaluator::default(), 4, SearchLimits::default());
+        let_assert!(Some((Action::Place(moved_hexo), score)) = searcher.search_with_score(&state));
+        check!(is_middle_column(moved_hexo));
+        check!(score >= WIN_SCORE);
+    }
+
+    #[test]
+    fn returns_a_legal_action_when_out_of_nodes() {
+        let state = State::new_random_place(1);
+        let mut searcher = AlphaBeta::new(Evaluator::default(), 10, SearchLimits::nodes(50));
+        let_assert!(Some(action) = searcher.search(&state));
+        check!(state.legal_actions().any(|legal| legal == action));
+    }
+}
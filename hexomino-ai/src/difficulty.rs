@@ -0,0 +1,118 @@
+use std::{fmt, time::Duration};
+
+use hexomino_core::RandomPlayer;
+
+use super::{
+    alpha_beta::AlphaBeta,
+    eval::{Evaluator, Weights},
+    limits::SearchLimits,
+    mcts::Mcts,
+    Searcher,
+};
+
+/// The named strengths of the AI, from the weakest to the strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Difficulty {
+    /// Plays random legal actions.
+    Beginner,
+    /// Picks the action that leaves it the most placements, without looking ahead.
+    Easy,
+    /// Looks two actions ahead with the full evaluation.
+    #[default]
+    Medium,
+    /// Runs a Monte Carlo tree search for up to a second.
+    Hard,
+    /// Searches as deep as it can in two seconds with the full evaluation.
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] = [
+        Difficulty::Beginner,
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Beginner => "Beginner",
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    /// Returns a searcher that plays at this difficulty. Its random choices only depend on the
+    /// seed.
+    pub fn searcher(self, seed: u64) -> Box<dyn Searcher> {
+        match self {
+            Difficulty::Beginner => Box::new(RandomPlayer::new(seed)),
+            Difficulty::Easy => {
+                let weights = Weights {
+                    mobility: 1,
+                    reserve: 0,
+                    private_regions: 0,
+                };
+                Box::new(AlphaBeta::new(
+                    Evaluator::new(weights),
+                    1,
+                    SearchLimits::time(Duration::from_millis(200)),
+                ))
+            }
+            Difficulty::Medium => Box::new(AlphaBeta::new(
+                Evaluator::default(),
+                2,
+                SearchLimits {
+                    max_nodes: Some(20_000),
+                    max_time: Some(Duration::from_millis(500)),
+                },
+            )),
+            Difficulty::Hard => Box::new(Mcts::new(
+                SearchLimits {
+                    max_nodes: Some(5_000),
+                    max_time: Some(Duration::from_secs(1)),
+                },
+                seed,
+            )),
+            Difficulty::Expert => Box::new(AlphaBeta::new(
+                Evaluator::default(),
+                16,
+                SearchLimits::time(Duration::from_secs(2)),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use hexomino_core::{BoardSpec, HexoSet, PieceSet, State};
+
+    use super::*;
+
+    #[test]
+    fn every_difficulty_plays_legal_actions() {
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        let game = State::with_pool(BoardSpec::new(6, 10).unwrap(), pool).unwrap();
+        for num_actions in [3, 15] {
+            let mut state = game.clone();
+            RandomPlayer::new(4).play_until(&mut state, num_actions);
+            for difficulty in Difficulty::ALL {
+                let_assert!(Some(action) = difficulty.searcher(5).search(&state));
+                check!(
+                    state.legal_actions().any(|legal| legal == action),
+                    "{difficulty}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use hexomino_core::{Action, GamePhase, Hexo, State};
+
+use super::Searcher;
+
+/// How much each feature of a hexo counts in [`DraftEvaluator::rank`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DraftWeights {
+    /// The weight of the placements of the hexo on the empty board, relative to the average of
+    /// the remaining hexos.
+    pub flexibility: f64,
+    /// The weight of the number of distinct orientations of the hexo, out of 8.
+    pub orientations: f64,
+    /// The weight of each hexo the player owns that has the same footprint as the hexo. It is
+    /// usually negative, so that the picks complement each other.
+    pub duplicates: f64,
+}
+
+impl Default for DraftWeights {
+    fn default() -> Self {
+        Self {
+            flexibility: 10.0,
+            orientations: 2.0,
+            duplicates: -1.5,
+        }
+    }
+}
+
+/// Why a hexo is ranked where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickReason {
+    /// The hexo fits in more places than the average remaining hexo.
+    Flexible { placements: usize },
+    /// The hexo fits in fewer places than the average remaining hexo.
+    Rigid { placements: usize },
+    /// The hexo has all 8 orientations, so it can adapt to odd-shaped holes.
+    Asymmetric,
+    /// The hexo has at most 2 orientations, so it needs holes of its exact shape.
+    Symmetric { orientations: usize },
+    /// No hexo the player owns has the same footprint.
+    NewFootprint { width: usize, height: usize },
+    /// The player already owns hexos with the same footprint.
+    SameFootprint {
+        width: usize,
+        height: usize,
+        owned: usize,
+    },
+}
+
+impl fmt::Display for PickReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickReason::Flexible { placements } => {
+                write!(
+                    f,
+                    "fits in {placements} places on an empty board, more than most"
+                )
+            }
+            PickReason::Rigid { placements } => {
+                write!(f, "fits in only {placements} places on an empty board")
+            }
+            PickReason::Asymmetric => write!(f, "has all 8 orientations"),
+            PickReason::Symmetric { orientations } => {
+                write!(f, "has only {orientations} orientations")
+            }
+            PickReason::NewFootprint { width, height } => {
+                write!(f, "is the first {width}x{height} piece you would own")
+            }
+            PickReason::SameFootprint {
+                width,
+                height,
+                owned,
+            } => write!(f, "you already own {owned} {width}x{height} pieces"),
+        }
+    }
+}
+
+/// A remaining hexo with its score for the player to pick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickRecommendation {
+    pub hexo: Hexo,
+    pub score: f64,
+    pub reasons: Vec<PickReason>,
+}
+
+/// Ranks the remaining hexos for the player to pick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DraftEvaluator {
+    pub weights: DraftWeights,
+}
+
+impl DraftEvaluator {
+    pub fn new(weights: DraftWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Returns the remaining hexos from the best pick to the worst, or nothing if the game is
+    /// not in the pick phase.
+    pub fn rank(&self, state: &State) -> Vec<PickRecommendation> {
+        let seat = match state.current_seat() {
+            Some(seat) if state.phase() == GamePhase::Pick => seat,
+            _ => return vec![],
+        };
+        let inventory = state.inventory();
+        let empty_board = State::with_piece_set(state.board().layout(), state.piece_set());
+        let remaining: Vec<_> = inventory
+            .remaining_hexos()
+            .iter()
+            .map(|hexo| (hexo, empty_board.board().num_placements(hexo)))
+            .collect();
+        let mean_placements = remaining
+            .iter()
+            .map(|&(_, placements)| placements as f64)
+            .sum::<f64>()
+            / remaining.len().max(1) as f64;
+
+        let mut recommendations: Vec<_> = remaining
+            .into_iter()
+            .map(|(hexo, placements)| {
+                let mut reasons = vec![];
+                let flexibility = if mean_placements > 0.0 {
+                    placements as f64 / mean_placements - 1.0
+                } else {
+                    0.0
+                };
+                if flexibility >= 0.1 {
+                    reasons.push(PickReason::Flexible { placements });
+                } else if flexibility <= -0.1 {
+                    reasons.push(PickReason::Rigid { placements });
+                }
+
+                let orientations = hexo.orientations().len();
+                match orientations {
+                    8 => reasons.push(PickReason::Asymmetric),
+                    1 | 2 => reasons.push(PickReason::Symmetric { orientations }),
+                    _ => {}
+                }
+
+                let (width, height) = footprint(hexo);
+                let owned = inventory
+                    .hexos_of(seat)
+                    .iter()
+                    .filter(|&owned| footprint(owned) == (width, height))
+                    .count();
+                if owned > 0 {
+                    reasons.push(PickReason::SameFootprint {
+                        width,
+                        height,
+                        owned,
+                    });
+                } else if !inventory.hexos_of(seat).is_empty() {
+                    reasons.push(PickReason::NewFootprint { width, height });
+                }
+
+                let score = self.weights.flexibility * flexibility
+                    + self.weights.orientations * orientations as f64 / 8.0
+                    + self.weights.duplicates * owned as f64;
+                PickRecommendation {
+                    hexo,
+                    score,
+                    reasons,
+                }
+            })
+            .collect();
+        recommendations.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(a.hexo.id().cmp(&b.hexo.id()))
+        });
+        recommendations
+    }
+}
+
+/// Returns the width and the height of the bounding box of the hexo, the longer side first.
+fn footprint(hexo: Hexo) -> (usize, usize) {
+    let (xs, ys): (Vec<_>, Vec<_>) = hexo.tiles().map(|tile| (tile.x, tile.y)).unzip();
+    let size = |values: Vec<i32>| {
+        (values.iter().max().unwrap() - values.iter().min().unwrap() + 1) as usize
+    };
+    let (width, height) = (size(xs), size(ys));
+    (width.max(height), width.min(height))
+}
+
+impl Searcher for DraftEvaluator {
+    /// Picks the best ranked hexo. When banning, bans it instead, to keep it from the other
+    /// players. There is no action outside the pick phase.
+    fn search(&mut self, state: &State) -> Option<Action> {
+        let best = self.rank(state).first()?.hexo;
+        Some(if state.is_banning() {
+            Action::Ban(best)
+        } else {
+            Action::Pick(best)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use hexomino_core::{BoardSpec, HexoSet, PieceSet, Player};
+
+    use super::*;
+
+    fn pentomino_game() -> State {
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        State::with_pool(BoardSpec::new(6, 10).unwrap(), pool).unwrap()
+    }
+
+    fn hexo_with_footprint(state: &State, size: (usize, usize)) -> Hexo {
+        state
+            .piece_set()
+            .hexos()
+            .find(|&hexo| footprint(hexo) == size)
+            .unwrap()
+    }
+
+    #[test]
+    fn ranks_every_remaining_hexo() {
+        // The X pentomino has a single orientation, and fits in the fewest places.
+        let state = pentomino_game();
+        let ranking = DraftEvaluator::default().rank(&state);
+        check!(ranking.len() == 12);
+        check!(ranking
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        let_assert!(Some(last) = ranking.last());
+        check!(last.hexo.orientations().len() == 1);
+        check!(last
+            .reasons
+            .contains(&PickReason::Symmetric { orientations: 1 }));
+    }
+
+    #[test]
+    fn prefers_new_footprints() {
+        let mut state = pentomino_game();
+        // The first player picks the I pentomino, and the second player picks a 4x2 pentomino.
+        let i = hexo_with_footprint(&state, (5, 1));
+        state.current_player_play(Action::Pick(i)).unwrap();
+        let l = hexo_with_footprint(&state, (4, 2));
+        state.current_player_play(Action::Pick(l)).unwrap();
+        check!(state.current_player() == Some(Player::First));
+        let ranking = DraftEvaluator::default().rank(&state);
+        let same_as_l = ranking
+            .iter()
+            .find(|recommendation| footprint(recommendation.hexo) == (4, 2))
+            .unwrap();
+        check!(same_as_l.reasons.iter().any(|reason| matches!(
+            reason,
+            PickReason::NewFootprint {
+                width: 4,
+                height: 2
+            }
+        )));
+
+        // After picking a 4x2 piece, the other 4x2 pieces are marked as duplicates.
+        state
+            .current_player_play(Action::Pick(same_as_l.hexo))
+            .unwrap();
+        let square = hexo_with_footprint(&state, (3, 3));
+        state.current_player_play(Action::Pick(square)).unwrap();
+        let ranking = DraftEvaluator::default().rank(&state);
+        let duplicate = ranking
+            .iter()
+            .find(|recommendation| footprint(recommendation.hexo) == (4, 2))
+            .unwrap();
+        check!(duplicate.reasons.contains(&PickReason::SameFootprint {
+            width: 4,
+            height: 2,
+            owned: 1
+        }));
+    }
+
+    #[test]
+    fn no_recommendations_outside_the_pick_phase() {
+        let state = State::new_random_place(1);
+        check!(DraftEvaluator::default().rank(&state).is_empty());
+        check!(DraftEvaluator::default().search(&state) == None);
+    }
+}
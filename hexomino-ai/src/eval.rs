@@ -0,0 +1,143 @@
+use hexomino_core::{GamePhase, Seat, State};
+
+/// The score of a won game. Scores of positions that are not over stay far below it.
+pub const WIN_SCORE: i32 = 1_000_000;
+
+/// How much each feature of a position counts in [`Evaluator::evaluate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weights {
+    pub mobility: i32,
+    pub reserve: i32,
+    pub private_regions: i32,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            mobility: 1,
+            reserve: 10,
+            private_regions: 30,
+        }
+    }
+}
+
+/// The features of a position for one player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Features {
+    /// The number of distinct placements of the hexos the player owns.
+    pub mobility: i32,
+    /// The number of hexos the player owns that can still be placed somewhere.
+    pub reserve: i32,
+    /// The number of empty regions where a hexo of the player fits, but no hexo of any other
+    /// player does. The player can always place there later.
+    pub private_regions: i32,
+}
+
+impl Features {
+    pub fn of(state: &State, seat: impl Into<Seat>) -> Self {
+        Self::of_players(state)[seat.into().index()]
+    }
+
+    /// Returns the features of every player in the game, indexed by [`Seat::index`].
+    pub fn of_players(state: &State) -> Vec<Self> {
+        let board = state.board();
+        let inventory = state.inventory();
+        let mut features = vec![Features::default(); inventory.num_players()];
+        for seat in inventory.seats() {
+            for hexo in inventory.hexos_of(seat).iter() {
+                let placements = board.num_placements(hexo) as i32;
+                features[seat.index()].mobility += placements;
+                features[seat.index()].reserve += (placements > 0) as i32;
+            }
+        }
+        for region in board.regions() {
+            let mut fitting_seats = inventory.seats().filter(|&seat| {
+                inventory
+                    .hexos_of(seat)
+                    .iter()
+                    .any(|hexo| board.placements_in(hexo, &region).next().is_some())
+            });
+            if let (Some(seat), None) = (fitting_seats.next(), fitting_seats.next()) {
+                features[seat.index()].private_regions += 1;
+            }
+        }
+        features
+    }
+}
+
+/// Scores positions from the point of view of a player, by a weighted sum of the [`Features`]
+/// of the player against the best of the other players.
+///
+/// The features assume that placing more is better, as in [`WinRule::Normal`]. Finished games
+/// are scored by their winner under any rule.
+///
+/// [`WinRule::Normal`]: hexomino_core::WinRule::Normal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Evaluator {
+    pub weights: Weights,
+}
+
+impl Evaluator {
+    pub fn new(weights: Weights) -> Self {
+        Self { weights }
+    }
+
+    /// Returns the score of the state for the player in the seat. A higher score is better for
+    /// the player, [`WIN_SCORE`] is a win, and `-WIN_SCORE` is a loss.
+    pub fn evaluate(&self, state: &State, seat: impl Into<Seat>) -> i32 {
+        let seat = seat.into();
+        if state.phase() == GamePhase::End {
+            return match state.winning_seat() {
+                Some(winner) if winner == seat => WIN_SCORE,
+                Some(_) => -WIN_SCORE,
+                None => 0,
+            };
+        }
+        let score = |features: Features| {
+            self.weights.mobility * features.mobility
+                + self.weights.reserve * features.reserve
+                + self.weights.private_regions * features.private_regions
+        };
+        let features = Features::of_players(state);
+        let best_other = state
+            .inventory()
+            .seats()
+            .filter(|&other| other != seat && !state.eliminated().contains(&other))
+            .map(|other| score(features[other.index()]))
+            .max()
+            .unwrap_or(0);
+        score(features[seat.index()]) - best_other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+    use hexomino_core::{Action, Player};
+
+    use super::*;
+    use crate::testing::{domino_endgame, is_middle_column};
+
+    #[test]
+    fn features_count_placements_and_private_regions() {
+        let state = domino_endgame();
+        // The domino fits 7 ways on the 2x3 board, and the L 8 ways.
+        check!(Features::of(&state, Player::Second).mobility == 7);
+        check!(Features::of(&state, Player::First).mobility == 8);
+        check!(Features::of(&state, Player::First).private_regions == 0);
+    }
+
+    #[test]
+    fn finished_games_score_the_winner() {
+        let mut state = domino_endgame();
+        let action = state
+            .legal_actions()
+            .find(|action| matches!(action, Action::Place(moved_hexo) if is_middle_column(*moved_hexo)))
+            .unwrap();
+        state.current_player_play(action).unwrap();
+        let evaluator = Evaluator::default();
+        check!(state.winner() == Some(Player::Second));
+        check!(evaluator.evaluate(&state, Player::Second) == WIN_SCORE);
+        check!(evaluator.evaluate(&state, Player::First) == -WIN_SCORE);
+    }
+}
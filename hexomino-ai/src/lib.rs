@@ -0,0 +1,37 @@
+//! AI players for Hexomino, built on [`hexomino_core`].
+//!
+//! The searchers work on any [`State`], and pick the action for its current player. For a
+//! ready-made opponent, use one of the [`Difficulty`] levels.
+
+mod alpha_beta;
+mod difficulty;
+mod draft;
+mod eval;
+mod limits;
+mod mcts;
+mod solver;
+#[cfg(test)]
+mod testing;
+
+pub use alpha_beta::*;
+pub use difficulty::*;
+pub use draft::*;
+pub use eval::*;
+pub use limits::*;
+pub use mcts::*;
+pub use solver::*;
+
+use hexomino_core::{Action, RandomPlayer, State};
+
+/// Something that chooses actions for the current player of a game.
+pub trait Searcher {
+    /// Returns the action to play for the current player, or `None` if there is no legal
+    /// action.
+    fn search(&mut self, state: &State) -> Option<Action>;
+}
+
+impl Searcher for RandomPlayer {
+    fn search(&mut self, state: &State) -> Option<Action> {
+        self.choose(state)
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use instant::Instant;
+
+/// How long a search may run. The search stops at whichever limit is reached first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// The maximum number of nodes to visit. For [`Mcts`](crate::Mcts), this is the number of
+    /// playouts.
+    pub max_nodes: Option<u64>,
+    pub max_time: Option<Duration>,
+}
+
+impl SearchLimits {
+    pub fn nodes(max_nodes: u64) -> Self {
+        Self {
+            max_nodes: Some(max_nodes),
+            max_time: None,
+        }
+    }
+
+    pub fn time(max_time: Duration) -> Self {
+        Self {
+            max_nodes: None,
+            max_time: Some(max_time),
+        }
+    }
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self::nodes(10_000)
+    }
+}
+
+/// The nodes and time spent by a search so far.
+pub(crate) struct Budget {
+    limits: SearchLimits,
+    start: Instant,
+    nodes: u64,
+    exhausted: bool,
+}
+
+impl Budget {
+    pub(crate) fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            start: Instant::now(),
+            nodes: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Counts a visited node, and returns true if the search should stop.
+    pub(crate) fn tick(&mut self) -> bool {
+        self.nodes += 1;
+        if self.limits.max_nodes.map_or(false, |max| self.nodes > max) {
+            self.exhausted = true;
+        }
+        if self
+            .limits
+            .max_time
+            .map_or(false, |max| self.start.elapsed() >= max)
+        {
+            self.exhausted = true;
+        }
+        self.exhausted
+    }
+}
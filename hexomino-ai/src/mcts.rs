@@ -0,0 +1,153 @@
+use hexomino_core::{Action, RandomPlayer, Seat, State};
+
+use super::{
+    limits::{Budget, SearchLimits},
+    Searcher,
+};
+
+struct Node {
+    /// The action that leads to the node, and the player who plays it.
+    action: Option<(Action, Seat)>,
+    children: Vec<usize>,
+    untried_actions: Vec<Action>,
+    visits: u32,
+    /// The total reward of the playouts through the node, for the player who plays the action.
+    reward: f64,
+}
+
+impl Node {
+    fn new(state: &State, action: Option<(Action, Seat)>) -> Self {
+        Self {
+            action,
+            children: vec![],
+            untried_actions: state.legal_actions().collect(),
+            visits: 0,
+            reward: 0.0,
+        }
+    }
+}
+
+/// A Monte Carlo tree searcher, which picks actions by the UCT formula and evaluates positions
+/// by random playouts.
+///
+/// A playout scores 1 for the winner, 0 for the other players, and a half for everyone in a
+/// tie, so it works with any number of players and any win rule.
+pub struct Mcts {
+    limits: SearchLimits,
+    exploration: f64,
+    playout: RandomPlayer,
+}
+
+impl Mcts {
+    /// Creates a searcher whose playouts are driven by the seed.
+    pub fn new(limits: SearchLimits, seed: u64) -> Self {
+        Self {
+            limits,
+            exploration: std::f64::consts::SQRT_2,
+            playout: RandomPlayer::new(seed),
+        }
+    }
+
+    /// Sets the exploration constant of the UCT formula. Higher values try more actions that
+    /// look worse so far.
+    pub fn with_exploration(mut self, exploration: f64) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    fn select_child(&self, tree: &[Node], node: usize) -> usize {
+        let parent_visits = (tree[node].visits as f64).ln();
+        let uct = |child: &Node| {
+            let visits = child.visits as f64;
+            child.reward / visits + self.exploration * (parent_visits / visits).sqrt()
+        };
+        *tree[node]
+            .children
+            .iter()
+            .max_by(|&&a, &&b| uct(&tree[a]).total_cmp(&uct(&tree[b])))
+            .unwrap()
+    }
+}
+
+impl Searcher for Mcts {
+    fn search(&mut self, state: &State) -> Option<Action> {
+        let mut tree = vec![Node::new(state, None)];
+        match tree[0].untried_actions[..] {
+            [] => return None,
+            [action] => return Some(action),
+            _ => {}
+        }
+        let mut budget = Budget::new(self.limits);
+        let mut state = state.clone();
+        let root_len = state.actions().len();
+        while !budget.tick() {
+            // Select a leaf, expand it, and play out from there.
+            let mut path = vec![0];
+            let mut node = 0;
+            while tree[node].untried_actions.is_empty() && !tree[node].children.is_empty() {
+                node = self.select_child(&tree, node);
+                let (action, _) = tree[node].action.unwrap();
+                state.current_player_play(action).unwrap();
+                path.push(node);
+            }
+            if let Some(action) = tree[node].untried_actions.pop() {
+                let seat = state.current_seat().unwrap();
+                state.current_player_play(action).unwrap();
+                tree.push(Node::new(&state, Some((action, seat))));
+                let child = tree.len() - 1;
+                tree[node].children.push(child);
+                path.push(child);
+            }
+            self.playout.play_out(&mut state);
+
+            let winner = state.winning_seat();
+            for &node in &path {
+                let node = &mut tree[node];
+                node.visits += 1;
+                if let Some((_, seat)) = node.action {
+                    node.reward += match winner {
+                        Some(winner) if winner == seat => 1.0,
+                        Some(_) => 0.0,
+                        None => 0.5,
+                    };
+                }
+            }
+            while state.actions().len() > root_len {
+                state.undo();
+            }
+        }
+        let best_child = tree[0]
+            .children
+            .iter()
+            .max_by_key(|&&child| tree[child].visits);
+        match best_child {
+            Some(&child) => tree[child].action.map(|(action, _)| action),
+            None => tree[0].untried_actions.last().copied(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+    use crate::testing::{domino_endgame, is_middle_column};
+
+    #[test]
+    fn finds_the_only_winning_move() {
+        let state = domino_endgame();
+        let mut searcher = Mcts::new(SearchLimits::nodes(500), 1);
+        let_assert!(Some(Action::Place(moved_hexo)) = searcher.search(&state));
+        check!(is_middle_column(moved_hexo));
+    }
+
+    #[test]
+    fn same_seed_searches_the_same() {
+        let state = State::new_random(2, 40);
+        let search = |seed| Mcts::new(SearchLimits::nodes(30), seed).search(&state);
+        check!(search(3) == search(3));
+        let_assert!(Some(action) = search(3));
+        check!(state.legal_actions().any(|legal| legal == action));
+    }
+}
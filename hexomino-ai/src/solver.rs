@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use hexomino_core::{Action, GamePhase, MovedHexo, Player, Rules, State};
+use thiserror::Error;
+
+use super::{
+    limits::{Budget, SearchLimits},
+    Searcher,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum SolveError {
+    #[error("the game is not in the place phase")]
+    NotInPlacePhase,
+    #[error("only two-player games can be solved")]
+    UnsupportedPlayerCount,
+    #[error("the search ran out of nodes or time")]
+    OutOfBudget,
+}
+
+/// The result of a game under perfect play, for the player to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// A solved position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub outcome: Outcome,
+    /// The placements of perfect play until the game ends, starting with the player to move.
+    /// The player who loses delays the loss as long as the solver happens to find.
+    pub principal_variation: Vec<MovedHexo>,
+}
+
+/// What the table knows about a solved position.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// The winner under perfect play, or `None` for a draw.
+    winner: Option<Player>,
+    best_placement: MovedHexo,
+}
+
+/// An exact solver for the place phase of two-player games.
+///
+/// The solved positions are kept in a transposition table across calls with the same rules, so
+/// solving the positions of one game one after another is cheap. Call [`EndgameSolver::clear`]
+/// before moving on to another game if memory matters.
+pub struct EndgameSolver {
+    limits: SearchLimits,
+    table: HashMap<u128, Entry>,
+    /// The rules of the positions in the table, as the hash of a position does not cover them.
+    rules: Option<Rules>,
+}
+
+impl EndgameSolver {
+    const MAX_PLACEMENTS_TO_ORDER: usize = 256;
+
+    pub fn new(limits: SearchLimits) -> Self {
+        Self {
+            limits,
+            table: HashMap::new(),
+            rules: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+        self.rules = None;
+    }
+
+    /// Proves the outcome of the state for the player to move, and returns it with the
+    /// principal variation.
+    pub fn solve(&mut self, state: &State) -> Result<Solution, SolveError> {
+        if state.phase() != GamePhase::Place {
+            return Err(SolveError::NotInPlacePhase);
+        }
+        if state.num_players() != 2 {
+            return Err(SolveError::UnsupportedPlayerCount);
+        }
+        if self.rules != Some(state.rules()) {
+            self.clear();
+            self.rules = Some(state.rules());
+        }
+        let player = state.current_player().unwrap();
+        let mut budget = Budget::new(self.limits);
+        let mut state = state.clone();
+        let winner = self.winner(&mut state, &mut budget)?;
+        let outcome = match winner {
+            Some(winner) if winner == player => Outcome::Win,
+            Some(_) => Outcome::Loss,
+            None => Outcome::Draw,
+        };
+        let mut principal_variation = vec![];
+        while let Some(entry) = self.table.get(&state.zobrist_hash128()) {
+            principal_variation.push(entry.best_placement);
+            state
+                .current_player_play(Action::Place(entry.best_placement))
+                .unwrap();
+        }
+        Ok(Solution {
+            outcome,
+            principal_variation,
+        })
+    }
+
+    /// Solves every position of the place phase the game has gone through, and returns the
+    /// outcome for the player to move, indexed by the number of actions played before it.
+    ///
+    /// The positions are solved from the last one back, so the later positions help solve the
+    /// earlier ones. Positions that cannot be solved within the limits are reported as errors.
+    pub fn analyze(&mut self, state: &State) -> Vec<(usize, Result<Outcome, SolveError>)> {
+        let mut state = state.clone();
+        let mut outcomes = vec![];
+        loop {
+            if state.phase() == GamePhase::Place {
+                outcomes.push((
+                    state.actions().len(),
+                    self.solve(&state).map(|solution| solution.outcome),
+                ));
+            }
+            if state.undo().is_none() || state.phase() == GamePhase::Pick {
+                break;
+            }
+        }
+        outcomes.reverse();
+        outcomes
+    }
+
+    /// Returns the winner of the state under perfect play, or `None` for a draw.
+    fn winner(
+        &mut self,
+        state: &mut State,
+        budget: &mut Budget,
+    ) -> Result<Option<Player>, SolveError> {
+        let player = match state.current_player() {
+            Some(player) => player,
+            None => return Ok(state.winner()),
+        };
+        let key = state.zobrist_hash128();
+        if let Some(entry) = self.table.get(&key) {
+            return Ok(entry.winner);
+        }
+        if budget.tick() {
+            return Err(SolveError::OutOfBudget);
+        }
+
+        let mut best: Option<(Option<Player>, MovedHexo)> = None;
+        for placement in self.ordered_placements(state) {
+            state.current_player_play(Action::Place(placement)).unwrap();
+            let winner = self.winner(state, budget);
+            state.undo();
+            let winner = winner?;
+            let rank = |winner: Option<Player>| match winner {
+                Some(winner) if winner == player => 2,
+                None => 1,
+                Some(_) => 0,
+            };
+            if best.map_or(true, |(best_winner, _)| rank(winner) > rank(best_winner)) {
+                best = Some((winner, placement));
+            }
+            if winner == Some(player) {
+                break;
+            }
+        }
+        let (winner, best_placement) = best.expect("a player in turn can always place");
+        self.table.insert(
+            key,
+            Entry {
+                winner,
+                best_placement,
+            },
+        );
+        Ok(winner)
+    }
+
+    /// Returns the placements of the current player, the ones known to win first, and then the
+    /// ones that leave the fewest placements to the opponent.
+    ///
+    /// Ordering takes playing every placement, so positions with many placements, which are
+    /// too large to solve anyway, are left in their original order.
+    fn ordered_placements(&self, state: &mut State) -> Vec<MovedHexo> {
+        let player = state.current_player().unwrap();
+        let placements: Vec<_> = state
+            .legal_actions()
+            .filter_map(|action| match action {
+                Action::Place(placement) => Some(placement),
+                _ => None,
+            })
+            .collect();
+        if placements.len() > Self::MAX_PLACEMENTS_TO_ORDER {
+            return placements;
+        }
+        let mut ordered: Vec<_> = placements
+            .into_iter()
+            .map(|placement| {
+                state.current_player_play(Action::Place(placement)).unwrap();
+                let known_win = self
+                    .table
+                    .get(&state.zobrist_hash128())
+                    .map_or(false, |entry| entry.winner == Some(player));
+                let opponent = state.inventory().hexos_of(player.other());
+                let mobility: usize = opponent
+                    .iter()
+                    .map(|hexo| state.board().num_placements(hexo))
+                    .sum();
+                let wins_now = state.winner() == Some(player);
+                state.undo();
+                (!(wins_now || known_win), mobility, placement)
+            })
+            .collect();
+        ordered.sort_by_key(|&(not_winning, mobility, _)| (not_winning, mobility));
+        ordered
+            .into_iter()
+            .map(|(_, _, placement)| placement)
+            .collect()
+    }
+}
+
+impl Searcher for EndgameSolver {
+    /// Plays the first placement of the principal variation, or the first legal action if the
+    /// state cannot be solved.
+    fn search(&mut self, state: &State) -> Option<Action> {
+        match self.solve(state) {
+            Ok(solution) => solution
+                .principal_variation
+                .first()
+                .copied()
+                .map(Action::Place),
+            Err(_) => state.legal_actions().next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+    use hexomino_core::{BoardSpec, HexoSet, PieceSet, RandomPlayer, WinRule};
+
+    use super::*;
+    use crate::testing::{domino_endgame, is_middle_column};
+
+    /// Returns the winner under perfect play by plain minimax, without any table.
+    fn minimax_winner(state: &mut State) -> Option<Player> {
+        let player = match state.current_player() {
+            Some(player) => player,
+            None => return state.winner(),
+        };
+        let actions: Vec<_> = state.legal_actions().collect();
+        let mut winners = vec![];
+        for action in actions {
+            state.current_player_play(action).unwrap();
+            winners.push(minimax_winner(state));
+            state.undo();
+        }
+        if winners.contains(&Some(player)) {
+            Some(player)
+        } else if winners.contains(&None) {
+            None
+        } else {
+            Some(player.other())
+        }
+    }
+
+    #[test]
+    fn proves_the_only_winning_move() {
+        let state = domino_endgame();
+        let mut solver = EndgameSolver::new(SearchLimits::default());
+        let_assert!(Ok(solution) = solver.solve(&state));
+        check!(solution.outcome == Outcome::Win);
+        let_assert!([placement] = solution.principal_variation[..]);
+        check!(is_middle_column(placement));
+    }
+
+    #[test]
+    fn principal_variation_plays_to_the_end() {
+        let mut state = domino_endgame();
+        let losing = state
+            .legal_actions()
+            .find(|action| !matches!(action, Action::Place(placement) if is_middle_column(*placement)))
+            .unwrap();
+        state.current_player_play(losing).unwrap();
+        let mut solver = EndgameSolver::new(SearchLimits::default());
+        let_assert!(Ok(solution) = solver.solve(&state));
+        check!(solution.outcome == Outcome::Win);
+        for placement in solution.principal_variation {
+            state.current_player_play(Action::Place(placement)).unwrap();
+        }
+        check!(state.winner() == Some(Player::First));
+    }
+
+    #[test]
+    fn analyze_reports_every_place_phase_position() {
+        let mut state = domino_endgame();
+        let losing = state
+            .legal_actions()
+            .find(|action| !matches!(action, Action::Place(placement) if is_middle_column(*placement)))
+            .unwrap();
+        state.current_player_play(losing).unwrap();
+        let mut solver = EndgameSolver::new(SearchLimits::default());
+        // The second player was winning after the draft, and the first player is winning after
+        // the mistake.
+        check!(solver.analyze(&state) == [(2, Ok(Outcome::Win)), (3, Ok(Outcome::Win))]);
+    }
+
+    #[test]
+    fn positions_are_not_shared_across_rules() {
+        let normal = domino_endgame();
+        let rules = Rules {
+            win: WinRule::Misere,
+            ..Rules::default()
+        };
+        let initial = State::with_rules(normal.board().layout(), normal.pool(), rules).unwrap();
+        let actions: Vec<_> = normal.actions().collect();
+        let_assert!(Ok(misere) = State::replay(initial, &actions));
+        // Filling the middle column wins the normal game, but leaves the second player without a
+        // placement, which makes them win the misere game.
+        let mut solver = EndgameSolver::new(SearchLimits::default());
+        let_assert!(Ok(solution) = solver.solve(&normal));
+        check!(is_middle_column(solution.principal_variation[0]));
+        let_assert!(Ok(expected) = EndgameSolver::new(SearchLimits::default()).solve(&misere));
+        check!(expected.outcome == Outcome::Win);
+        check!(!is_middle_column(expected.principal_variation[0]));
+        check!(solver.solve(&misere) == Ok(expected));
+    }
+
+    #[test]
+    fn agrees_with_minimax_on_small_endgames() {
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        let game = State::with_pool(BoardSpec::new(5, 8).unwrap(), pool).unwrap();
+        let mut solver = EndgameSolver::new(SearchLimits::default());
+        let mut num_solved = 0;
+        for seed in 0..64 {
+            let mut state = game.clone();
+            RandomPlayer::new(seed).play_until(&mut state, 16);
+            if state.phase() != GamePhase::Place || num_solved == 8 {
+                continue;
+            }
+            let player = state.current_player().unwrap();
+            let_assert!(Ok(solution) = solver.solve(&state));
+            let expected = match minimax_winner(&mut state.clone()) {
+                Some(winner) if winner == player => Outcome::Win,
+                Some(_) => Outcome::Loss,
+                None => Outcome::Draw,
+            };
+            check!(solution.outcome == expected, "seed {seed}");
+            num_solved += 1;
+        }
+        check!(num_solved == 8);
+    }
+
+    #[test]
+    fn rejects_unsolvable_states() {
+        let mut solver = EndgameSolver::new(SearchLimits::nodes(10));
+        check!(solver.solve(&State::new()) == Err(SolveError::NotInPlacePhase));
+        check!(solver.solve(&State::new_random_place(1)) == Err(SolveError::OutOfBudget));
+    }
+}
@@ -0,0 +1,25 @@
+use hexomino_core::{Action, BoardSpec, HexoSet, MovedHexo, PieceSet, Pos, State};
+
+/// Returns a game on a 2x3 board, where the second player is to place a domino and the first
+/// player holds an L tromino. The only winning move is the domino standing in the middle column,
+/// which leaves no room for the L.
+pub(crate) fn domino_endgame() -> State {
+    let tiles = |cells: &[(i32, i32)]| cells.iter().copied().map(Pos::from).collect();
+    let piece_set = PieceSet::new(vec![
+        tiles(&[(0, 0), (1, 0), (0, 1)]),
+        tiles(&[(0, 0), (1, 0)]),
+    ])
+    .unwrap();
+    let mut state =
+        State::with_pool(BoardSpec::new(2, 3).unwrap(), HexoSet::all(piece_set)).unwrap();
+    for hexo in piece_set.hexos() {
+        state.current_player_play(Action::Pick(hexo)).unwrap();
+    }
+    state
+}
+
+pub(crate) fn is_middle_column(moved_hexo: MovedHexo) -> bool {
+    let mut tiles: Vec<_> = moved_hexo.tiles().collect();
+    tiles.sort();
+    tiles == [Pos::new(1, 0), Pos::new(1, 1)]
+}
@@ -94,6 +94,15 @@ derive_api_data! {
     }
 }
 
+impl From<GameEndReason> for hexomino_core::EndReason {
+    fn from(reason: GameEndReason) -> Self {
+        match reason {
+            GameEndReason::NoValidMove => Self::NoValidMove,
+            GameEndReason::TimeLimitExceed => Self::TimeLimitExceed,
+        }
+    }
+}
+
 derive_api_data! {
     pub struct SyncMatchApi;
     pub struct MatchActionApi;
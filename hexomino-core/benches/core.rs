@@ -0,0 +1,93 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use hexomino_core::{Action, RandomPlayer, State};
+
+/// The positions the benchmarks start from: right after a random draft, and in the middle of
+/// the place phase.
+fn positions() -> [(&'static str, State); 2] {
+    [
+        ("draft", State::new_random_place(1)),
+        ("middle", State::new_random(1, 50)),
+    ]
+}
+
+fn placement_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("placement_search");
+    for (name, state) in positions() {
+        let hexos: Vec<_> = state
+            .inventory()
+            .hexos_of(state.current_player().unwrap())
+            .iter()
+            .collect();
+        let board = state.board();
+        group.bench_function(format!("try_find_placement/{name}"), |b| {
+            b.iter(|| {
+                for &hexo in &hexos {
+                    black_box(board.try_find_placement(hexo));
+                }
+            })
+        });
+        group.bench_function(format!("num_placements/{name}"), |b| {
+            b.iter(|| {
+                for &hexo in &hexos {
+                    black_box(board.num_placements(hexo));
+                }
+            })
+        });
+        let placements: Vec<_> = hexos
+            .iter()
+            .flat_map(|&hexo| board.placements(hexo).take(16))
+            .collect();
+        group.bench_function(format!("can_place/{name}"), |b| {
+            b.iter(|| {
+                for placement in &placements {
+                    black_box(board.can_place(placement));
+                }
+            })
+        });
+        let mut state = state.clone();
+        let action = Action::Place(placements[0]);
+        group.bench_function(format!("play_and_undo/{name}"), |b| {
+            b.iter(|| {
+                state.current_player_play(action).unwrap();
+                state.undo();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn random_playout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random_playout");
+    group.bench_function("from_start", |b| {
+        let mut seed = 0;
+        b.iter(|| {
+            seed += 1;
+            black_box(State::new_random_end(seed))
+        })
+    });
+    let start = State::new_random_place(1);
+    group.bench_function("from_draft", |b| {
+        let mut seed = 0;
+        b.iter_batched(
+            || start.clone(),
+            |mut state| {
+                seed += 1;
+                RandomPlayer::new(seed).play_out(&mut state);
+                state
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn state_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("state_clone");
+    for (name, state) in positions() {
+        group.bench_function(name, |b| b.iter(|| black_box(&state).clone()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, placement_search, random_playout, state_clone);
+criterion_main!(benches);
@@ -1,13 +1,44 @@
-use std::{env, fs, path::Path, process::Command};
+use std::{env, fs, path::Path};
+
+#[path = "src/polyomino.rs"]
+mod polyomino;
+
+use polyomino::Cell;
+
+fn pos_repr((x, y): Cell) -> String {
+    format!("Pos {{ x: {}, y: {} }}", x, y)
+}
+
+fn hexo_repr(hexo: &[Cell]) -> String {
+    let tiles = hexo.iter().map(|&p| pos_repr(p)).collect::<Vec<_>>();
+    let borders = polyomino::borders(hexo)
+        .into_iter()
+        .map(|(p1, p2)| format!("({}, {})", pos_repr(p1), pos_repr(p2)))
+        .collect::<Vec<_>>();
+    format!(
+        "__Hexo {{\n            tiles: [{}],\n            borders: &[{}],\n        }}",
+        tiles.join(", "),
+        borders.join(", ")
+    )
+}
 
 fn main() {
-    let output = Command::new("python3")
-        .args(["gen_hexos/gen.py"])
-        .output()
-        .expect("failed to run python script to generate hexominos");
+    if let Err(err) = polyomino::check_hexos() {
+        panic!("invalid hexo table: {}", err);
+    }
+    let hexos = polyomino::HEXOS
+        .iter()
+        .map(|hexo| hexo_repr(hexo))
+        .collect::<Vec<_>>();
+    let n_hexos = hexos.len();
+    let code = format!(
+        "#[cfg(not(test))]\npub const N_HEXOS: usize = {n_hexos};\n\
+         #[cfg(not(test))]\npub const HEXOS: [__Hexo; {n_hexos}] = [\n    {}\n];\n",
+        hexos.join(",\n    ")
+    );
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("hexos.rs");
-    fs::write(&dest_path, output.stdout).expect("failed to write to \"hexos.rs\"");
+    fs::write(&dest_path, code).expect("failed to write to \"hexos.rs\"");
     println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=gen_hexos/gen.py");
+    println!("cargo:rerun-if-changed=src/polyomino.rs");
 }
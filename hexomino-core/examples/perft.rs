@@ -0,0 +1,42 @@
+//! Counts the legal actions to a depth from a position played at random.
+//!
+//! Usage: `cargo run --release --example perft -- <seed> <num_actions> <depth> [pentominoes]`
+//!
+//! The position is a standard game, or a game of the pentominoes on a 6x10 board, played at
+//! random with the seed until `num_actions` actions have been played.
+
+use std::{env, process, time::Instant};
+
+use hexomino_core::{BoardSpec, HexoSet, PieceSet, RandomPlayer, State};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parse = |index: usize| -> u64 {
+        args.get(index)
+            .and_then(|arg| arg.parse().ok())
+            .unwrap_or_else(|| {
+                eprintln!("usage: perft <seed> <num_actions> <depth> [pentominoes]");
+                process::exit(2);
+            })
+    };
+    let (seed, num_actions, depth) = (parse(0), parse(1) as usize, parse(2) as usize);
+    let mut state = match args.get(3).map(String::as_str) {
+        Some("pentominoes") => State::with_pool(
+            BoardSpec::new(6, 10).unwrap(),
+            HexoSet::all(PieceSet::pentominoes()),
+        )
+        .unwrap(),
+        _ => State::new(),
+    };
+    RandomPlayer::new(seed).play_until(&mut state, num_actions);
+
+    let start = Instant::now();
+    let mut total = 0;
+    for (action, count) in state.perft_divide(depth) {
+        println!("{action}: {count}");
+        total += count;
+    }
+    let elapsed = start.elapsed();
+    println!();
+    println!("{total} leaves at depth {depth} in {elapsed:.2?}");
+}
@@ -0,0 +1,104 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+const WORDS: usize = 4;
+
+/// A set of tiles on the board, indexed by [`BoardSpec::tile_index`](crate::BoardSpec::tile_index).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Bitboard {
+    words: [u64; WORDS],
+}
+
+impl Bitboard {
+    /// The maximum number of tiles a bitboard can hold.
+    pub const CAPACITY: usize = WORDS * 64;
+    pub const EMPTY: Self = Self { words: [0; WORDS] };
+
+    pub fn has(&self, idx: usize) -> bool {
+        self.words[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    pub fn set(&mut self, idx: usize) {
+        self.words[idx / 64] |= 1 << (idx % 64);
+    }
+
+    pub fn clear(&mut self, idx: usize) {
+        self.words[idx / 64] &= !(1 << (idx % 64));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns true if the two bitboards have a tile in common.
+    pub fn intersects(&self, other: &Bitboard) -> bool {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .any(|(a, b)| a & b != 0)
+    }
+
+    /// Returns the indices of the tiles in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+impl FromIterator<usize> for Bitboard {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut bitboard = Bitboard::EMPTY;
+        for idx in iter {
+            bitboard.set(idx);
+        }
+        bitboard
+    }
+}
+
+macro_rules! impl_bit_op_for_bitboard {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl $op for Bitboard {
+            type Output = Bitboard;
+            fn $fn(mut self, rhs: Bitboard) -> Bitboard {
+                self.$fn_assign(rhs);
+                self
+            }
+        }
+
+        impl $op_assign for Bitboard {
+            fn $fn_assign(&mut self, rhs: Bitboard) {
+                for (a, b) in self.words.iter_mut().zip(rhs.words) {
+                    a.$fn_assign(b);
+                }
+            }
+        }
+    };
+}
+
+impl_bit_op_for_bitboard!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op_for_bitboard!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op_for_bitboard!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+    fn not(self) -> Bitboard {
+        Bitboard {
+            words: self.words.map(|word| !word),
+        }
+    }
+}
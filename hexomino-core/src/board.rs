@@ -1,79 +1,451 @@
+use std::{collections::HashMap, fmt, sync::Mutex};
+
 use super::{
-    constants::{COLS, ROWS},
+    bitboard::Bitboard,
+    board_layout::BoardLayout,
+    board_spec::BoardSpec,
     hexo::{Hexo, MovedHexo, PlacedHexo},
+    orientation::Orientation,
+    piece_set::PieceSet,
     pos::Pos,
+    region::Region,
+    snapshot::SnapshotError,
+    state::Seat,
+    zobrist::{self, N_BOARD_SYMMETRIES},
 };
 use itertools::Itertools;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// A position of a hexo that is inside the board, with the mask of the tiles it covers.
+struct PlacementMask {
+    orientation: &'static Orientation,
+    anchor: Pos,
+    mask: Bitboard,
+}
+
+/// The in-bound placements of a hexo.
+struct HexoPlacements {
+    /// The placements, in the order of the orientations and then of the anchors.
+    masks: Vec<PlacementMask>,
+    /// The index in `masks` of the placement of each orientation at each anchor, indexed by the
+    /// orientation index times the number of tiles plus [`BoardSpec::tile_index`] of the anchor,
+    /// or `None` if the placement is out of bounds.
+    by_anchor: Vec<Option<u16>>,
+}
+
+/// The in-bound placements of every hexo, indexed by the hexo id.
+type PlacementMasks = [HexoPlacements];
+
+/// The placement masks of each board spec and piece set that are used.
+///
+/// The masks are computed when a board of the spec and piece set is first created, and are
+/// leaked so that boards can refer to them without reference counting.
+static PLACEMENT_MASKS: Lazy<Mutex<HashMap<(BoardSpec, PieceSet), &'static PlacementMasks>>> =
+    Lazy::new(Default::default);
+
+fn placement_masks(spec: BoardSpec, piece_set: PieceSet) -> &'static PlacementMasks {
+    let mut cache = PLACEMENT_MASKS.lock().unwrap();
+    cache.entry((spec, piece_set)).or_insert_with(|| {
+        let masks = piece_set
+            .hexos()
+            .map(|hexo| {
+                let orientations = hexo.orientations();
+                let mut placements = HexoPlacements {
+                    masks: vec![],
+                    by_anchor: vec![None; orientations.len() * spec.num_tiles()],
+                };
+                for ((idx, orientation), anchor) in orientations
+                    .iter()
+                    .enumerate()
+                    .cartesian_product(spec.all_tiles())
+                {
+                    let in_bound = orientation
+                        .tiles()
+                        .iter()
+                        .all(|tile| spec.in_bound(tile + anchor));
+                    if !in_bound {
+                        continue;
+                    }
+                    let index = idx * spec.num_tiles() + spec.tile_index(anchor);
+                    placements.by_anchor[index] = Some(placements.masks.len() as u16);
+                    placements.masks.push(PlacementMask {
+                        orientation,
+                        anchor,
+                        mask: orientation
+                            .tiles()
+                            .iter()
+                            .map(|tile| spec.tile_index(tile + anchor))
+                            .collect(),
+                    });
+                }
+                placements
+            })
+            .collect::<Vec<_>>();
+        Box::leak(masks.into_boxed_slice())
+    })
+}
+
+/// The tiles of a hexo that prevent it from being placed on a board.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlacementConflicts {
+    /// The tiles that are outside the board.
+    pub out_of_bounds: Vec<Pos>,
+    /// The tiles that are blocked by the layout of the board.
+    pub blocked: Vec<Pos>,
+    /// The tiles that are covered by a placed hexo, with the seat of the player who placed it.
+    pub overlaps: Vec<(Pos, Seat)>,
+}
+
+impl PlacementConflicts {
+    pub fn is_empty(&self) -> bool {
+        self.out_of_bounds.is_empty() && self.blocked.is_empty() && self.overlaps.is_empty()
+    }
 
-#[derive(Debug, Clone)]
+    /// Returns true if the tile is one of the conflicts.
+    pub fn contains(&self, tile: Pos) -> bool {
+        self.out_of_bounds.contains(&tile)
+            || self.blocked.contains(&tile)
+            || self.overlaps.iter().any(|(pos, _)| *pos == tile)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "BoardRepr", into = "BoardRepr")]
 pub struct Board {
-    board: [[bool; ROWS]; COLS],
+    layout: BoardLayout,
+    piece_set: PieceSet,
+    placement_masks: &'static PlacementMasks,
+    /// The tiles that are blocked or covered by a hexo.
+    occupied: Bitboard,
     placed_hexos: Vec<PlacedHexo>,
+    /// The Zobrist hashes of the occupied tiles, viewed under each symmetry of the board.
+    hashes: [u128; N_BOARD_SYMMETRIES],
 }
 
-impl Board {
-    pub(super) fn new() -> Self {
+/// The occupied tiles and the hashes are determined by the layout and the placed hexos.
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.layout == other.layout
+            && self.piece_set == other.piece_set
+            && self.placed_hexos == other.placed_hexos
+    }
+}
+
+impl Eq for Board {}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Board")
+            .field("layout", &self.layout)
+            .field("piece_set", &self.piece_set)
+            .field("occupied", &self.occupied)
+            .field("placed_hexos", &self.placed_hexos)
+            .finish()
+    }
+}
+
+/// The serialized form of [`Board`], from which the rest is recomputed.
+#[derive(Serialize, Deserialize)]
+struct BoardRepr {
+    #[serde(default)]
+    spec: BoardSpec,
+    #[serde(default)]
+    blocked: Vec<Pos>,
+    #[serde(default)]
+    piece_set: PieceSet,
+    placed_hexos: Vec<PlacedHexo>,
+}
+
+impl From<Board> for BoardRepr {
+    fn from(board: Board) -> Self {
         Self {
-            board: [[false; ROWS]; COLS],
+            spec: board.spec(),
+            blocked: board.layout.blocked_tiles().collect(),
+            piece_set: board.piece_set,
+            placed_hexos: board.placed_hexos,
+        }
+    }
+}
+
+impl TryFrom<BoardRepr> for Board {
+    type Error = SnapshotError;
+
+    fn try_from(repr: BoardRepr) -> Result<Self, Self::Error> {
+        let layout = BoardLayout::new(repr.spec, repr.blocked)
+            .map_err(|_| SnapshotError::Malformed("blocked tile is outside the board"))?;
+        Self::from_placed_hexos(layout, repr.piece_set, repr.placed_hexos)
+    }
+}
+
+impl Board {
+    pub(super) fn new(layout: BoardLayout, piece_set: PieceSet) -> Self {
+        let mut board = Self {
+            layout,
+            piece_set,
+            placement_masks: placement_masks(layout.spec(), piece_set),
+            occupied: Bitboard::EMPTY,
             placed_hexos: vec![],
+            hashes: [0; N_BOARD_SYMMETRIES],
+        };
+        for tile in layout.blocked_tiles() {
+            board.mark_placed(tile);
         }
+        board
+    }
+
+    /// Creates a board by placing the hexos in order.
+    pub(crate) fn from_placed_hexos(
+        layout: BoardLayout,
+        piece_set: PieceSet,
+        placed_hexos: impl IntoIterator<Item = PlacedHexo>,
+    ) -> Result<Self, SnapshotError> {
+        let mut board = Self::new(layout, piece_set);
+        for hexo in placed_hexos {
+            if hexo.moved_hexo().hexo().piece_set() != piece_set {
+                return Err(SnapshotError::PieceSetMismatch);
+            }
+            board
+                .place(hexo)
+                .map_err(|_| SnapshotError::CannotPlaceHexo(*hexo.moved_hexo()))?;
+        }
+        Ok(board)
+    }
+
+    pub fn spec(&self) -> BoardSpec {
+        self.layout.spec()
+    }
+
+    pub fn layout(&self) -> BoardLayout {
+        self.layout
+    }
+
+    /// Returns true if the tile is blocked by the layout of the board.
+    pub fn is_blocked(&self, tile: Pos) -> bool {
+        self.layout.is_blocked(tile)
+    }
+
+    /// Returns the set of the pieces placed on the board.
+    pub fn piece_set(&self) -> PieceSet {
+        self.piece_set
+    }
+
+    pub fn in_bound(&self, point: Pos) -> bool {
+        self.spec().in_bound(point)
+    }
+
+    /// Returns the index of an in-bound tile in a [`Bitboard`].
+    pub fn tile_index(&self, tile: Pos) -> usize {
+        self.spec().tile_index(tile)
     }
 
-    pub fn in_bound(point: Pos) -> bool {
-        0 <= point.x && point.x < COLS as i32 && 0 <= point.y && point.y < ROWS as i32
+    /// Returns the tile at an index of a [`Bitboard`].
+    pub fn tile_at(&self, idx: usize) -> Pos {
+        self.spec().tile_at(idx)
     }
 
+    /// Returns all the tiles of the board.
+    pub fn all_tiles(&self) -> impl Iterator<Item = Pos> + Clone {
+        self.spec().all_tiles()
+    }
+
+    /// Returns true if the tile is blocked or covered by a hexo.
     pub fn is_placed(&self, point: Pos) -> bool {
-        assert!(Self::in_bound(point));
-        self.board[point.x as usize][point.y as usize]
+        self.occupied.has(self.tile_index(point))
+    }
+
+    /// Returns the tiles that are blocked or covered by a hexo.
+    pub fn occupied(&self) -> Bitboard {
+        self.occupied
+    }
+
+    /// Returns the Zobrist hash of the occupied tiles.
+    pub fn zobrist_hash(&self) -> u128 {
+        self.hashes[0]
+    }
+
+    /// Returns a Zobrist hash of the occupied tiles that is the same for boards that are mirror
+    /// images or 180 degree rotations of each other.
+    pub fn symmetric_zobrist_hash(&self) -> u128 {
+        self.hashes.into_iter().min().unwrap()
+    }
+
+    /// Returns the index of the tile under each symmetry of the board.
+    fn symmetric_tile_indices(&self, tile: Pos) -> [usize; N_BOARD_SYMMETRIES] {
+        let opposite = self.spec().opposite(tile);
+        [
+            tile,
+            Pos::new(opposite.x, tile.y),
+            Pos::new(tile.x, opposite.y),
+            opposite,
+        ]
+        .map(|tile| self.tile_index(tile))
+    }
+
+    fn toggle_hashes(&mut self, tile: Pos) {
+        let indices = self.symmetric_tile_indices(tile);
+        for (hash, idx) in self.hashes.iter_mut().zip(indices) {
+            *hash ^= zobrist::tile_key(idx);
+        }
     }
 
     fn mark_placed(&mut self, tile: Pos) {
-        assert!(Self::in_bound(tile));
         assert!(!self.is_placed(tile));
-        self.board[tile.x as usize][tile.y as usize] = true;
+        self.occupied.set(self.tile_index(tile));
+        self.toggle_hashes(tile);
     }
 
-    fn all_tiles(&self) -> impl Iterator<Item = Pos> {
-        (0..COLS)
-            .cartesian_product(0..ROWS)
-            .map(|(x, y)| Pos::new(x as i32, y as i32))
+    fn unmark_placed(&mut self, tile: Pos) {
+        assert!(self.is_placed(tile));
+        self.occupied.clear(self.tile_index(tile));
+        self.toggle_hashes(tile);
     }
 
-    fn all_empty_tiles(&self) -> impl Iterator<Item = Pos> + '_ {
-        self.all_tiles()
-            .filter(move |point| !self.is_placed(*point))
+    /// Returns true if the hexo is inside the board and covers no blocked or covered tile.
+    pub fn can_place(&self, hexo: &MovedHexo) -> bool {
+        self.placement_mask(hexo)
+            .map_or(false, |mask| !mask.intersects(&self.occupied))
     }
 
-    pub fn can_place(&self, hexo: &MovedHexo) -> bool {
-        hexo.tiles()
-            .all(|tile| Self::in_bound(tile) && !self.is_placed(tile))
+    /// Returns the mask of the tiles the hexo covers, unless it is partly outside the board or
+    /// of another piece set.
+    fn placement_mask(&self, moved_hexo: &MovedHexo) -> Option<&'static Bitboard> {
+        let hexo = moved_hexo.hexo();
+        let anchor = moved_hexo.anchor();
+        if hexo.piece_set() != self.piece_set || !self.in_bound(anchor) {
+            return None;
+        }
+        let placements = &self.placement_masks[hexo.id()];
+        let index = moved_hexo.rhexo().orientation_idx() * self.spec().num_tiles()
+            + self.tile_index(anchor);
+        let mask_index = placements.by_anchor[index]?;
+        Some(&placements.masks[mask_index as usize].mask)
     }
 
-    /// Returns true if there is a position on the board the hexo can be placed.
-    pub fn try_find_placement(&self, hexo: Hexo) -> Option<MovedHexo> {
-        for rhexo in hexo.all_orbit() {
-            for pos in self.all_empty_tiles() {
-                let moved_hexo = rhexo.move_to(pos);
-                if self.can_place(&moved_hexo) {
-                    return Some(moved_hexo);
-                }
+    /// Returns the seat of the player who placed the hexo covering the tile, if there is any.
+    ///
+    /// A blocked tile is owned by no player.
+    pub fn owner_at(&self, tile: Pos) -> Option<Seat> {
+        if !self.in_bound(tile) || !self.is_placed(tile) || self.is_blocked(tile) {
+            return None;
+        }
+        self.placed_hexos
+            .iter()
+            .find(|placed_hexo| placed_hexo.moved_hexo().tiles().any(|t| t == tile))
+            .map(|placed_hexo| placed_hexo.seat())
+    }
+
+    /// Returns the number of tiles covered by the hexos the player in the seat placed.
+    pub fn area_of(&self, seat: impl Into<Seat>) -> usize {
+        let seat = seat.into();
+        self.placed_hexos
+            .iter()
+            .filter(|placed_hexo| placed_hexo.seat() == seat)
+            .map(|placed_hexo| placed_hexo.moved_hexo().tiles().count())
+            .sum()
+    }
+
+    /// Returns the tiles of the hexo that are outside the board, blocked, or already covered.
+    ///
+    /// The hexo can be placed if and only if there is no conflict.
+    pub fn placement_conflicts(&self, hexo: &MovedHexo) -> PlacementConflicts {
+        let mut conflicts = PlacementConflicts::default();
+        for tile in hexo.tiles() {
+            if !self.in_bound(tile) {
+                conflicts.out_of_bounds.push(tile);
+            } else if self.is_blocked(tile) {
+                conflicts.blocked.push(tile);
+            } else if let Some(owner) = self.owner_at(tile) {
+                conflicts.overlaps.push((tile, owner));
             }
         }
-        None
+        conflicts
+    }
+
+    /// Returns an error describing why the hexo cannot be placed, if it cannot.
+    pub(super) fn check_placement(&self, hexo: &MovedHexo) -> crate::state::Result<()> {
+        if self.can_place(hexo) {
+            return Ok(());
+        }
+        let PlacementConflicts {
+            out_of_bounds,
+            blocked,
+            overlaps,
+        } = self.placement_conflicts(hexo);
+        if !out_of_bounds.is_empty() {
+            return Err(crate::Error::HexoOutOfBounds {
+                moved_hexo: *hexo,
+                tiles: out_of_bounds,
+            });
+        }
+        if !blocked.is_empty() {
+            return Err(crate::Error::HexoOnBlockedTiles {
+                moved_hexo: *hexo,
+                tiles: blocked,
+            });
+        }
+        Err(crate::Error::HexoOverlaps {
+            moved_hexo: *hexo,
+            overlaps,
+        })
+    }
+
+    /// Returns a position on the board the hexo can be placed, if there is any.
+    pub fn try_find_placement(&self, hexo: Hexo) -> Option<MovedHexo> {
+        self.placements(hexo).next()
     }
 
     /// Returns true if there is a position on the board the hexo can be placed.
     pub fn can_place_somewhere(&self, hexo: Hexo) -> bool {
-        self.try_find_placement(hexo).is_some()
+        self.free_placement_masks(hexo).next().is_some()
     }
 
-    pub(super) fn place(&mut self, hexo: PlacedHexo) -> crate::state::Result<()> {
-        if !self.can_place(hexo.moved_hexo()) {
-            return Err(crate::Error::CannotPlaceHexo {
-                moved_hexo: *hexo.moved_hexo(),
-            });
+    /// Returns the placement masks of the hexo, which are none if it is of another piece set.
+    fn placement_masks_of(&self, hexo: Hexo) -> &'static [PlacementMask] {
+        if hexo.piece_set() == self.piece_set {
+            &self.placement_masks[hexo.id()].masks
+        } else {
+            &[]
         }
+    }
+
+    fn free_placement_masks(
+        &self,
+        hexo: Hexo,
+    ) -> impl Iterator<Item = &'static PlacementMask> + '_ {
+        self.placement_masks_of(hexo)
+            .iter()
+            .filter(move |placement| !placement.mask.intersects(&self.occupied))
+    }
+
+    /// Returns all the distinct positions on the board the hexo can be placed.
+    ///
+    /// Two returned placements never cover the same set of tiles.
+    pub fn placements(&self, hexo: Hexo) -> impl Iterator<Item = MovedHexo> + '_ {
+        self.free_placement_masks(hexo)
+            .map(move |placement| placement.orientation.place_at(hexo, placement.anchor))
+    }
+
+    /// Returns the distinct positions the hexo can be placed that are entirely inside the
+    /// region.
+    pub fn placements_in<'a>(
+        &'a self,
+        hexo: Hexo,
+        region: &'a Region,
+    ) -> impl Iterator<Item = MovedHexo> + 'a {
+        self.placement_masks_of(hexo)
+            .iter()
+            .filter(move |placement| (placement.mask & !region.mask()).is_empty())
+            .map(move |placement| placement.orientation.place_at(hexo, placement.anchor))
+    }
+
+    /// Returns the number of distinct positions on the board the hexo can be placed.
+    pub fn num_placements(&self, hexo: Hexo) -> usize {
+        self.free_placement_masks(hexo).count()
+    }
+
+    pub(super) fn place(&mut self, hexo: PlacedHexo) -> crate::state::Result<()> {
+        self.check_placement(hexo.moved_hexo())?;
         for point in hexo.moved_hexo().tiles() {
             self.mark_placed(point);
         }
@@ -81,6 +453,15 @@ impl Board {
         Ok(())
     }
 
+    /// Removes the last placed hexo from the board.
+    pub(super) fn unplace(&mut self) -> Option<PlacedHexo> {
+        let hexo = self.placed_hexos.pop()?;
+        for tile in hexo.moved_hexo().tiles() {
+            self.unmark_placed(tile);
+        }
+        Some(hexo)
+    }
+
     pub fn placed_hexos(&self) -> &[PlacedHexo] {
         &self.placed_hexos
     }
@@ -89,45 +470,172 @@ impl Board {
 #[cfg(test)]
 mod test {
     use super::*;
-    use assert2::check;
+    use crate::{HexoSet, Player, Transform};
+    use assert2::{assert, check, let_assert};
+
+    const ROWS: usize = 4;
+    const COLS: usize = 6;
+
+    fn small_board() -> Board {
+        Board::new(
+            BoardSpec::new(ROWS, COLS).unwrap().into(),
+            PieceSet::default(),
+        )
+    }
+
+    fn board_from_grid(grid: [[bool; ROWS]; COLS]) -> Board {
+        let mut board = small_board();
+        for (x, column) in grid.iter().enumerate() {
+            for (y, placed) in column.iter().enumerate() {
+                if *placed {
+                    board.mark_placed(Pos::new(x as i32, y as i32));
+                }
+            }
+        }
+        board
+    }
+
     #[test]
     fn board_can_place_in_any() {
-        let board = Board {
-            board: [
-                [true, true, true, true],
-                [true, true, true, true],
-                [true, true, false, false],
-                [false, false, false, false],
-                [true, true, true, true],
-                [true, true, true, true],
-            ],
-            placed_hexos: vec![],
-        };
+        let board = board_from_grid([
+            [true, true, true, true],
+            [true, true, true, true],
+            [true, true, false, false],
+            [false, false, false, false],
+            [true, true, true, true],
+            [true, true, true, true],
+        ]);
         check!(board.can_place_somewhere(Hexo::new(0)));
-        let board = Board {
-            board: [
-                [true, true, false, true],
-                [true, true, false, true],
-                [true, true, false, false],
-                [true, true, false, false],
-                [true, true, true, true],
-                [true, true, true, true],
-            ],
-            placed_hexos: vec![],
-        };
+        let board = board_from_grid([
+            [true, true, false, true],
+            [true, true, false, true],
+            [true, true, false, false],
+            [true, true, false, false],
+            [true, true, true, true],
+            [true, true, true, true],
+        ]);
         check!(board.can_place_somewhere(Hexo::new(0)));
 
-        let board = Board {
-            board: [
-                [false, true, false, true],
-                [false, false, false, false],
-                [true, false, true, false],
-                [false, false, false, false],
-                [false, true, false, true],
-                [false, false, false, false],
-            ],
-            placed_hexos: vec![],
-        };
+        let board = board_from_grid([
+            [false, true, false, true],
+            [false, false, false, false],
+            [true, false, true, false],
+            [false, false, false, false],
+            [false, true, false, true],
+            [false, false, false, false],
+        ]);
         check!(!board.can_place_somewhere(Hexo::new(0)));
     }
+
+    #[test]
+    fn placement_masks_agree_with_can_place() {
+        let board = board_from_grid([
+            [false, true, false, false],
+            [false, false, false, false],
+            [false, false, true, false],
+            [false, false, false, false],
+            [false, false, false, false],
+            [true, false, false, false],
+        ]);
+        for hexo in Hexo::all_hexos() {
+            let mut count = 0;
+            for rhexo in hexo.distinct_orbit() {
+                for x in -6..12 {
+                    for y in -6..12 {
+                        let moved_hexo = rhexo.move_to(Pos::new(x, y));
+                        let free = moved_hexo
+                            .tiles()
+                            .all(|tile| board.in_bound(tile) && !board.is_placed(tile));
+                        check!(board.can_place(&moved_hexo) == free);
+                        if free {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+            check!(board.num_placements(hexo) == count);
+            check!(board
+                .placements(hexo)
+                .all(|moved_hexo| board.can_place(&moved_hexo)));
+        }
+    }
+
+    #[test]
+    fn symmetric_hash_ignores_mirroring() {
+        let mut board = small_board();
+        board.mark_placed(Pos::new(0, 0));
+        board.mark_placed(Pos::new(1, 0));
+        let mut mirrored = small_board();
+        mirrored.mark_placed(Pos::new(COLS as i32 - 1, 0));
+        mirrored.mark_placed(Pos::new(COLS as i32 - 2, 0));
+        let mut rotated = small_board();
+        rotated.mark_placed(Pos::new(COLS as i32 - 1, ROWS as i32 - 1));
+        rotated.mark_placed(Pos::new(COLS as i32 - 2, ROWS as i32 - 1));
+        let mut shifted = small_board();
+        shifted.mark_placed(Pos::new(1, 0));
+        shifted.mark_placed(Pos::new(2, 0));
+
+        check!(board.zobrist_hash() != mirrored.zobrist_hash());
+        check!(board.symmetric_zobrist_hash() == mirrored.symmetric_zobrist_hash());
+        check!(board.symmetric_zobrist_hash() == rotated.symmetric_zobrist_hash());
+        check!(board.symmetric_zobrist_hash() != shifted.symmetric_zobrist_hash());
+
+        board.unmark_placed(Pos::new(0, 0));
+        board.unmark_placed(Pos::new(1, 0));
+        check!(board.zobrist_hash() == small_board().zobrist_hash());
+    }
+
+    #[test]
+    fn placement_conflicts_name_offending_tiles() {
+        let mut board = small_board();
+        let placed = Hexo::new(1)
+            .apply(Transform::new(false, 1))
+            .place_at(Pos::new(0, 0));
+        assert!(let Ok(_) = board.place(placed.placed_by(Player::Second)));
+        check!(board.owner_at(Pos::new(3, 0)) == Some(Player::Second.into()));
+        check!(board.owner_at(Pos::new(3, 1)) == None);
+
+        let hexo = Hexo::new(1).apply(Transform::I).place_at(Pos::new(2, -1));
+        let conflicts = board.placement_conflicts(&hexo);
+        check!(conflicts.out_of_bounds == [Pos::new(2, -1), Pos::new(2, 4)]);
+        let second = Seat::from(Player::Second);
+        check!(conflicts.overlaps == [(Pos::new(2, 0), second)]);
+        check!(conflicts.contains(Pos::new(2, 0)));
+        check!(!conflicts.contains(Pos::new(2, 1)));
+        let_assert!(
+            Err(crate::Error::HexoOutOfBounds { tiles, .. }) = board.check_placement(&hexo)
+        );
+        check!(tiles == [Pos::new(2, -1), Pos::new(2, 4)]);
+
+        let hexo = Hexo::new(0).apply(Transform::I).place_at(Pos::new(2, 0));
+        let_assert!(
+            Err(crate::Error::HexoOverlaps { overlaps, .. }) = board.check_placement(&hexo)
+        );
+        check!(overlaps == [(Pos::new(2, 0), second), (Pos::new(3, 0), second)]);
+        check!(board
+            .placements(Hexo::new(0))
+            .all(|hexo| board.placement_conflicts(&hexo).is_empty()));
+    }
+
+    #[test]
+    fn placements_follow_board_spec() {
+        let board = Board::new(BoardSpec::STANDARD.into(), PieceSet::default());
+        check!(board.num_placements(Hexo::new(0)) == 4 * 17 * 9 + 4 * 15 * 11);
+        check!(board.num_placements(Hexo::new(1)) == 18 * 7 + 13 * 12);
+        check!(board.in_bound(Pos::new(17, 11)));
+        check!(!small_board().in_bound(Pos::new(17, 11)));
+        check!(small_board().num_placements(Hexo::new(1)) == 4);
+    }
+
+    #[test]
+    fn hexos_of_another_set_have_no_placement() {
+        let board = small_board();
+        let pentomino = PieceSet::pentominoes().hexo(0).unwrap();
+        check!(board.num_placements(pentomino) == 0);
+        check!(!board.can_place_somewhere(pentomino));
+        check!(board.try_find_placement(pentomino).is_none());
+        let mut hexos = HexoSet::empty(PieceSet::default());
+        hexos.add(pentomino);
+        check!(hexos.is_empty());
+    }
 }
@@ -0,0 +1,199 @@
+use std::{fmt, str::FromStr};
+
+use super::{bitboard::Bitboard, board_spec::BoardSpec, pos::Pos};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    #[error("the layout has no row")]
+    Empty,
+    #[error("row {row} has {found} tiles, but the first row has {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("{found:?} at row {row}, column {col} is neither `.` nor `#`")]
+    InvalidTile { row: usize, col: usize, found: char },
+    #[error(
+        "board of {rows}x{cols} is not supported, as a board has at most {} tiles",
+        Bitboard::CAPACITY
+    )]
+    UnsupportedSize { rows: usize, cols: usize },
+    #[error("blocked tile {0:?} is outside the board")]
+    OutOfBounds(Pos),
+}
+
+/// The shape of a board: its dimensions and the tiles that are blocked from the start.
+///
+/// No hexo can be placed on a blocked tile, so blocked tiles are owned by neither player.
+///
+/// A layout is written as its rows from the top, separated by `/` or line breaks, with `.` for an
+/// open tile and `#` for a blocked tile. For example, `....../..##../......` is a board of 3 rows
+/// and 6 columns with two blocked tiles in the centre.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct BoardLayout {
+    spec: BoardSpec,
+    blocked: Bitboard,
+}
+
+impl BoardLayout {
+    /// Creates a layout with the blocked tiles.
+    pub fn new(
+        spec: BoardSpec,
+        blocked: impl IntoIterator<Item = Pos>,
+    ) -> Result<Self, LayoutError> {
+        let mut layout = Self::from(spec);
+        for tile in blocked {
+            if !spec.in_bound(tile) {
+                return Err(LayoutError::OutOfBounds(tile));
+            }
+            layout.blocked.set(spec.tile_index(tile));
+        }
+        Ok(layout)
+    }
+
+    pub fn spec(&self) -> BoardSpec {
+        self.spec
+    }
+
+    /// Returns the blocked tiles, indexed by [`BoardSpec::tile_index`].
+    pub fn blocked(&self) -> Bitboard {
+        self.blocked
+    }
+
+    pub fn is_blocked(&self, tile: Pos) -> bool {
+        self.spec.in_bound(tile) && self.blocked.has(self.spec.tile_index(tile))
+    }
+
+    /// Returns the blocked tiles.
+    pub fn blocked_tiles(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.blocked.iter().map(|idx| self.spec.tile_at(idx))
+    }
+}
+
+impl From<BoardSpec> for BoardLayout {
+    fn from(spec: BoardSpec) -> Self {
+        Self {
+            spec,
+            blocked: Bitboard::EMPTY,
+        }
+    }
+}
+
+impl Default for BoardLayout {
+    fn default() -> Self {
+        BoardSpec::default().into()
+    }
+}
+
+impl fmt::Display for BoardLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.spec.rows() {
+            if y > 0 {
+                f.write_str("/")?;
+            }
+            for x in 0..self.spec.cols() {
+                let blocked = self.is_blocked(Pos::new(x as i32, y as i32));
+                f.write_str(if blocked { "#" } else { "." })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BoardLayout {
+    type Err = LayoutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.trim().split(['/', '\n']).map(str::trim).collect();
+        let cols = rows[0].chars().count();
+        if cols == 0 {
+            return Err(LayoutError::Empty);
+        }
+        let mut blocked = vec![];
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != cols {
+                return Err(LayoutError::RaggedRow {
+                    row: y,
+                    expected: cols,
+                    found: row.chars().count(),
+                });
+            }
+            for (x, c) in row.chars().enumerate() {
+                match c {
+                    '.' => {}
+                    '#' => blocked.push(Pos::new(x as i32, y as i32)),
+                    _ => {
+                        return Err(LayoutError::InvalidTile {
+                            row: y,
+                            col: x,
+                            found: c,
+                        })
+                    }
+                }
+            }
+        }
+        let spec = BoardSpec::new(rows.len(), cols).ok_or(LayoutError::UnsupportedSize {
+            rows: rows.len(),
+            cols,
+        })?;
+        Self::new(spec, blocked)
+    }
+}
+
+impl TryFrom<String> for BoardLayout {
+    type Error = LayoutError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<BoardLayout> for String {
+    fn from(layout: BoardLayout) -> Self {
+        layout.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    #[test]
+    fn layout_round_trips_through_text() {
+        let_assert!(Ok(layout) = "....../..##../......".parse::<BoardLayout>());
+        check!(layout.spec() == BoardSpec::new(3, 6).unwrap());
+        check!(layout.blocked_tiles().collect::<Vec<_>>() == [Pos::new(2, 1), Pos::new(3, 1)]);
+        check!(layout.to_string() == "....../..##../......");
+        check!("......\n..##..\n......\n".parse() == Ok(layout));
+        check!(BoardLayout::from(BoardSpec::new(2, 3).unwrap()).to_string() == ".../...");
+    }
+
+    #[test]
+    fn invalid_layouts_are_rejected() {
+        check!("".parse::<BoardLayout>() == Err(LayoutError::Empty));
+        check!(
+            "..../..."
+                .parse::<BoardLayout>()
+                .map_err(|err| err.to_string())
+                == Err("row 1 has 3 tiles, but the first row has 4".to_string())
+        );
+        check!(
+            "..x.".parse::<BoardLayout>()
+                == Err(LayoutError::InvalidTile {
+                    row: 0,
+                    col: 2,
+                    found: 'x'
+                })
+        );
+        let spec = BoardSpec::new(2, 2).unwrap();
+        check!(
+            BoardLayout::new(spec, [Pos::new(2, 0)])
+                == Err(LayoutError::OutOfBounds(Pos::new(2, 0)))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use super::{
+    bitboard::Bitboard,
+    constants::{COLS, ROWS},
+    pos::Pos,
+};
+use getset::CopyGetters;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// The dimensions of a board.
+///
+/// A board can have at most [`Bitboard::CAPACITY`] tiles, and at most [`BoardSpec::MAX_SIDE`]
+/// rows or columns. The default is the standard board of [`ROWS`] x [`COLS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, CopyGetters, Serialize, Deserialize)]
+#[serde(try_from = "BoardSpecRepr", into = "BoardSpecRepr")]
+pub struct BoardSpec {
+    #[getset(get_copy = "pub")]
+    rows: usize,
+    #[getset(get_copy = "pub")]
+    cols: usize,
+}
+
+/// The serialized form of [`BoardSpec`], which is validated when deserialized.
+#[derive(Serialize, Deserialize)]
+struct BoardSpecRepr {
+    rows: usize,
+    cols: usize,
+}
+
+impl From<BoardSpec> for BoardSpecRepr {
+    fn from(spec: BoardSpec) -> Self {
+        Self {
+            rows: spec.rows,
+            cols: spec.cols,
+        }
+    }
+}
+
+impl TryFrom<BoardSpecRepr> for BoardSpec {
+    type Error = String;
+
+    fn try_from(repr: BoardSpecRepr) -> Result<Self, Self::Error> {
+        Self::new(repr.rows, repr.cols).ok_or_else(|| {
+            format!(
+                "board of {}x{} is not supported, as a board has at most {} tiles",
+                repr.rows,
+                repr.cols,
+                Bitboard::CAPACITY
+            )
+        })
+    }
+}
+
+impl Default for BoardSpec {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl BoardSpec {
+    /// The standard board of [`ROWS`] x [`COLS`].
+    pub const STANDARD: Self = Self {
+        rows: ROWS,
+        cols: COLS,
+    };
+
+    /// The most rows or columns a board can have, so that each fits in a byte of a
+    /// [`Snapshot`](crate::Snapshot).
+    pub const MAX_SIDE: usize = u8::MAX as usize;
+
+    /// Returns the spec of a board with `rows` rows and `cols` columns, or `None` if the board is
+    /// empty or too large.
+    ///
+    /// A board is too large if it has more than [`Bitboard::CAPACITY`] tiles, or a side longer
+    /// than [`BoardSpec::MAX_SIDE`]. The limit is the same for games of more players, so e.g.
+    /// the largest square board is 16x16, and a board of 12 rows has at most 21 columns.
+    pub const fn new(rows: usize, cols: usize) -> Option<Self> {
+        if rows == 0 || cols == 0 || rows > Self::MAX_SIDE || cols > Self::MAX_SIDE {
+            return None;
+        }
+        match rows.checked_mul(cols) {
+            Some(num_tiles) if num_tiles <= Bitboard::CAPACITY => Some(Self { rows, cols }),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of tiles on the board.
+    pub fn num_tiles(self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn in_bound(self, point: Pos) -> bool {
+        0 <= point.x && point.x < self.cols as i32 && 0 <= point.y && point.y < self.rows as i32
+    }
+
+    /// Returns the index of an in-bound tile in a [`Bitboard`].
+    pub fn tile_index(self, tile: Pos) -> usize {
+        assert!(self.in_bound(tile));
+        tile.y as usize * self.cols + tile.x as usize
+    }
+
+    /// Returns the tile at an index of a [`Bitboard`].
+    pub fn tile_at(self, idx: usize) -> Pos {
+        assert!(idx < self.num_tiles());
+        Pos::new((idx % self.cols) as i32, (idx / self.cols) as i32)
+    }
+
+    /// Returns all the tiles of the board, column by column.
+    pub fn all_tiles(self) -> impl Iterator<Item = Pos> + Clone {
+        (0..self.cols)
+            .cartesian_product(0..self.rows)
+            .map(|(x, y)| Pos::new(x as i32, y as i32))
+    }
+
+    /// Returns the tile at the opposite corner, i.e. the tile after rotating the board 180
+    /// degrees.
+    pub(crate) fn opposite(self, tile: Pos) -> Pos {
+        Pos::new(self.cols as i32 - 1 - tile.x, self.rows as i32 - 1 - tile.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    #[test]
+    fn board_spec_rejects_unsupported_sizes() {
+        check!(BoardSpec::new(0, 6) == None);
+        check!(BoardSpec::new(16, 17) == None);
+        check!(BoardSpec::new(16, 16).is_some());
+        check!(BoardSpec::new(1, 255).is_some());
+        check!(BoardSpec::new(1, 256) == None);
+        check!(BoardSpec::new(usize::MAX, 2) == None);
+        check!(BoardSpec::default() == BoardSpec::new(12, 18).unwrap());
+    }
+
+    #[test]
+    fn tile_index_round_trips() {
+        for spec in [BoardSpec::STANDARD, BoardSpec::new(4, 6).unwrap()] {
+            check!(spec.all_tiles().count() == spec.num_tiles());
+            for tile in spec.all_tiles() {
+                check!(spec.tile_at(spec.tile_index(tile)) == tile);
+            }
+        }
+    }
+}
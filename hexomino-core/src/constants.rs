@@ -1,22 +1,17 @@
 use crate::Pos;
-use crate::Tiles;
 
 pub struct __Hexo {
-    pub tiles: Tiles,
+    pub tiles: [Pos; 6],
     pub borders: &'static [(Pos, Pos)],
 }
 
-#[cfg(not(test))]
+/// The number of rows of the standard board.
 pub const ROWS: usize = 12;
-#[cfg(not(test))]
+/// The number of columns of the standard board.
 pub const COLS: usize = 18;
 
 include!(concat!(env!("OUT_DIR"), "/hexos.rs"));
 
-#[cfg(test)]
-pub const ROWS: usize = 4;
-#[cfg(test)]
-pub const COLS: usize = 6;
 #[cfg(test)]
 pub const N_HEXOS: usize = 2;
 #[cfg(test)]
@@ -0,0 +1,222 @@
+//! An exact cover solver with Knuth's dancing links (Algorithm X).
+//!
+//! The nodes of the sparse matrix live in one array and link to each other by index. Node 0 is
+//! the root, and nodes `1..=num_columns` are the column headers.
+
+/// An exact cover problem: choose rows such that every column is covered by exactly one of
+/// them.
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node.
+    column: Vec<usize>,
+    /// The row of each node, which is unused for the root and the headers.
+    row: Vec<usize>,
+    /// The number of nodes in each column, indexed by the header.
+    size: Vec<usize>,
+    num_rows: usize,
+}
+
+impl ExactCover {
+    pub fn new(num_columns: usize) -> Self {
+        let num_nodes = num_columns + 1;
+        let mut problem = Self {
+            left: (0..num_nodes)
+                .map(|i| (i + num_nodes - 1) % num_nodes)
+                .collect(),
+            right: (0..num_nodes).map(|i| (i + 1) % num_nodes).collect(),
+            up: (0..num_nodes).collect(),
+            down: (0..num_nodes).collect(),
+            column: (0..num_nodes).collect(),
+            row: vec![usize::MAX; num_nodes],
+            size: vec![0; num_nodes],
+            num_rows: 0,
+        };
+        problem.size[0] = usize::MAX;
+        problem
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.size.len() - 1
+    }
+
+    /// Adds a row that covers the columns, and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column is out of range or repeated.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let row = self.num_rows;
+        self.num_rows += 1;
+        let first = self.left.len();
+        for (i, &column) in columns.iter().enumerate() {
+            assert!(
+                column < self.num_columns(),
+                "column {column} is out of range"
+            );
+            assert!(
+                !columns[..i].contains(&column),
+                "column {column} is repeated"
+            );
+            let header = column + 1;
+            let node = self.left.len();
+            self.left.push(if i == 0 { node } else { node - 1 });
+            self.right.push(first);
+            if i > 0 {
+                self.right[node - 1] = node;
+                self.left[first] = node;
+            }
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let last = self.up[header];
+            self.down[last] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.row.push(row);
+            self.size[header] += 1;
+        }
+        row
+    }
+
+    /// Returns up to `limit` solutions, each as the indices of the chosen rows in increasing
+    /// order.
+    pub fn solve(&mut self, limit: usize) -> Vec<Vec<usize>> {
+        let mut solutions = vec![];
+        let mut chosen = vec![];
+        self.search(limit, &mut chosen, &mut solutions);
+        solutions
+    }
+
+    /// Returns the number of solutions, counting at most to `limit`.
+    pub fn count_solutions(&mut self, limit: usize) -> usize {
+        self.solve(limit).len()
+    }
+
+    fn search(&mut self, limit: usize, chosen: &mut Vec<usize>, solutions: &mut Vec<Vec<usize>>) {
+        if solutions.len() >= limit {
+            return;
+        }
+        if self.right[0] == 0 {
+            let mut solution: Vec<_> = chosen.iter().map(|&node| self.row[node]).collect();
+            solution.sort_unstable();
+            solutions.push(solution);
+            return;
+        }
+        // Branch on the column with the fewest rows.
+        let mut header = self.right[0];
+        let mut column = header;
+        while column != 0 {
+            if self.size[column] < self.size[header] {
+                header = column;
+            }
+            column = self.right[column];
+        }
+        if self.size[header] == 0 {
+            return;
+        }
+
+        self.cover(header);
+        let mut node = self.down[header];
+        while node != header {
+            chosen.push(node);
+            let mut other = self.right[node];
+            while other != node {
+                self.cover(self.column[other]);
+                other = self.right[other];
+            }
+            self.search(limit, chosen, solutions);
+            let mut other = self.left[node];
+            while other != node {
+                self.uncover(self.column[other]);
+                other = self.left[other];
+            }
+            chosen.pop();
+            if solutions.len() >= limit {
+                break;
+            }
+            node = self.down[node];
+        }
+        self.uncover(header);
+    }
+
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+        let mut node = self.down[header];
+        while node != header {
+            let mut other = self.right[node];
+            while other != node {
+                self.down[self.up[other]] = self.down[other];
+                self.up[self.down[other]] = self.up[other];
+                self.size[self.column[other]] -= 1;
+                other = self.right[other];
+            }
+            node = self.down[node];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut node = self.up[header];
+        while node != header {
+            let mut other = self.left[node];
+            while other != node {
+                self.size[self.column[other]] += 1;
+                self.down[self.up[other]] = other;
+                self.up[self.down[other]] = other;
+                other = self.left[other];
+            }
+            node = self.up[node];
+        }
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+
+    #[test]
+    fn solves_knuths_example() {
+        // The example from "Dancing Links", whose only solution is rows 0, 3 and 4.
+        let mut problem = ExactCover::new(7);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row);
+        }
+        check!(problem.solve(10) == [vec![0, 3, 4]]);
+        // Solving leaves the matrix as it was.
+        check!(problem.solve(10) == [vec![0, 3, 4]]);
+    }
+
+    #[test]
+    fn counts_solutions_up_to_the_limit() {
+        // Every row covers a single column, and there are two rows per column.
+        let mut problem = ExactCover::new(3);
+        for column in [0, 0, 1, 1, 2, 2] {
+            problem.add_row(&[column]);
+        }
+        check!(problem.count_solutions(100) == 8);
+        check!(problem.count_solutions(3) == 3);
+        problem.add_row(&[0, 1, 2]);
+        check!(problem.count_solutions(100) == 9);
+    }
+
+    #[test]
+    fn no_solution_without_rows_for_a_column() {
+        let mut problem = ExactCover::new(2);
+        problem.add_row(&[0]);
+        check!(problem.solve(1).is_empty());
+    }
+}
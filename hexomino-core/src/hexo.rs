@@ -1,29 +1,57 @@
 use super::{
-    constants::{HEXOS, N_HEXOS},
+    piece_set::PieceSet,
     pos::Pos,
-    state::Player,
+    state::{Player, Seat},
+    zobrist::SplitMix64,
 };
 use getset::{CopyGetters, Getters};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Hexo(usize);
+/// A piece of a [`PieceSet`].
+///
+/// Despite the name, the piece is not necessarily a hexomino. Pieces of different sets are never
+/// equal.
+///
+/// A hexo of a built-in set is serialized as its id, plus the index of its set shifted left by 8
+/// bits, so the hexos of the standard set are serialized as their ids. A hexo of any other set
+/// is serialized with its set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "HexoRepr", into = "HexoRepr")]
+pub struct Hexo {
+    piece_set: PieceSet,
+    id: u8,
+}
 
-pub type Tiles = [Pos; 6];
+/// The tiles of a piece.
+pub type Tiles = Vec<Pos>;
 
 impl Hexo {
+    /// Returns the hexo of the standard set with the id.
     pub fn new(hexo_id: usize) -> Self {
-        assert!(hexo_id < N_HEXOS);
-        Hexo(hexo_id)
+        PieceSet::hexominoes()
+            .hexo(hexo_id)
+            .expect("hexo id should be in range")
+    }
+
+    pub(crate) fn from_parts(piece_set: PieceSet, id: usize) -> Self {
+        Self {
+            piece_set,
+            id: id as u8,
+        }
     }
 
+    /// The id of the hexo within its set.
     pub fn id(self) -> usize {
-        self.0
+        self.id as usize
+    }
+
+    pub fn piece_set(self) -> PieceSet {
+        self.piece_set
     }
 
     pub fn tiles(self) -> impl Iterator<Item = Pos> {
-        HEXOS[self.id()].tiles.iter().copied()
+        self.piece_set.piece(self.id()).tiles.iter().copied()
     }
 
     pub fn apply(self, transform: Transform) -> RHexo {
@@ -31,26 +59,101 @@ impl Hexo {
     }
 
     pub fn all_orbit(self) -> impl Iterator<Item = RHexo> {
-        IntoIterator::into_iter([false, true])
-            .cartesian_product(0..4)
-            .map(move |(flipped, rotate)| self.apply(Transform { flipped, rotate }))
+        Transform::all().map(move |transform| self.apply(transform))
     }
 
+    /// Returns the transformed hexos in the orbit that cover distinct shapes, i.e. no two of
+    /// them are equal up to translation.
+    pub fn distinct_orbit(self) -> impl Iterator<Item = RHexo> {
+        self.orientations()
+            .iter()
+            .map(move |orientation| self.apply(orientation.transform()))
+    }
+
+    /// Returns all the hexos of the standard set.
     pub fn all_hexos() -> impl Iterator<Item = Self> {
-        (0..N_HEXOS).map(Hexo::new)
+        PieceSet::hexominoes().hexos()
     }
 
     pub fn borders(self) -> impl Iterator<Item = (Pos, Pos)> {
-        HEXOS[self.id()].borders.iter().copied()
+        self.piece_set.piece(self.id()).borders.iter().copied()
+    }
+}
+
+/// Converts the id of a hexo, plus the [`PieceSet::index`] of its set shifted left by 8 bits.
+impl TryFrom<usize> for Hexo {
+    type Error = String;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        PieceSet::from_index(value >> 8)
+            .and_then(|piece_set| piece_set.hexo(value & 0xff))
+            .ok_or_else(|| format!("{value} is not a valid hexo id"))
+    }
+}
+
+impl From<Hexo> for usize {
+    fn from(hexo: Hexo) -> Self {
+        hexo.piece_set.index() << 8 | hexo.id()
+    }
+}
+
+/// The serialized form of [`Hexo`].
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum HexoRepr {
+    BuiltIn(usize),
+    Custom { piece_set: PieceSet, id: usize },
+}
+
+impl TryFrom<HexoRepr> for Hexo {
+    type Error = String;
+
+    fn try_from(repr: HexoRepr) -> Result<Self, Self::Error> {
+        match repr {
+            HexoRepr::BuiltIn(value) => Self::try_from(value)
+                .ok()
+                .filter(|hexo| hexo.piece_set.is_built_in())
+                .ok_or_else(|| format!("{value} is not a hexo of a built-in set")),
+            HexoRepr::Custom { piece_set, id } => piece_set
+                .hexo(id)
+                .ok_or_else(|| format!("{id} is not a valid hexo id")),
+        }
     }
 }
 
-#[derive(Default, Clone, Copy, Debug, Serialize, Deserialize)]
+impl From<Hexo> for HexoRepr {
+    fn from(hexo: Hexo) -> Self {
+        if hexo.piece_set.is_built_in() {
+            Self::BuiltIn(hexo.into())
+        } else {
+            Self::Custom {
+                piece_set: hexo.piece_set,
+                id: hexo.id(),
+            }
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "TransformRepr")]
 pub struct Transform {
     flipped: bool,
     rotate: i32,
 }
 
+/// The serialized form of [`Transform`], which is normalized when deserialized.
+#[derive(Deserialize)]
+struct TransformRepr {
+    flipped: bool,
+    rotate: i32,
+}
+
+impl From<TransformRepr> for Transform {
+    fn from(TransformRepr { flipped, rotate }: TransformRepr) -> Self {
+        Transform::new(flipped, rotate)
+    }
+}
+
 impl Transform {
     pub const I: Self = Transform {
         flipped: false,
@@ -60,10 +163,28 @@ impl Transform {
     pub fn new(flipped: bool, rotate: i32) -> Self {
         Self {
             flipped,
-            rotate: rotate % 4,
+            rotate: rotate.rem_euclid(4),
         }
     }
 
+    /// Returns all the eight transforms, unflipped ones first.
+    pub fn all() -> impl Iterator<Item = Self> {
+        IntoIterator::into_iter([false, true])
+            .cartesian_product(0..4)
+            .map(|(flipped, rotate)| Transform { flipped, rotate })
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self.flipped as usize * 4 + self.rotate as usize
+    }
+
+    pub(crate) fn from_index(idx: usize) -> Option<Self> {
+        (idx < 8).then_some(Transform {
+            flipped: idx >= 4,
+            rotate: (idx % 4) as i32,
+        })
+    }
+
     pub fn flip(self) -> Self {
         let Self { flipped, rotate } = self;
         Self {
@@ -80,7 +201,7 @@ impl Transform {
         }
     }
 
-    fn apply_on(self, mut tile: Pos) -> Pos {
+    pub(crate) fn apply_on(self, mut tile: Pos) -> Pos {
         for _ in 0..self.rotate {
             tile = tile.rotate();
         }
@@ -167,8 +288,8 @@ impl MovedHexo {
         self.rhexo.tiles().map(move |tile| tile + self.displacement)
     }
 
-    pub fn placed_by(self, player: Player) -> PlacedHexo {
-        PlacedHexo::new(self, player)
+    pub fn placed_by(self, seat: impl Into<Seat>) -> PlacedHexo {
+        PlacedHexo::new(self, seat)
     }
 
     pub fn borders(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
@@ -178,56 +299,148 @@ impl MovedHexo {
     }
 }
 
-#[derive(Debug, Clone, Copy, Getters, CopyGetters)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters, CopyGetters, Serialize, Deserialize)]
 pub struct PlacedHexo {
     #[getset(get = "pub")]
     moved_hexo: MovedHexo,
+    /// The seat of the player who placed the hexo.
     #[getset(get_copy = "pub")]
-    player: Player,
+    #[serde(rename = "player")]
+    seat: Seat,
 }
 
 impl PlacedHexo {
-    pub fn new(moved_hexo: MovedHexo, player: Player) -> Self {
-        Self { moved_hexo, player }
+    pub fn new(moved_hexo: MovedHexo, seat: impl Into<Seat>) -> Self {
+        Self {
+            moved_hexo,
+            seat: seat.into(),
+        }
+    }
+
+    /// Returns the player who placed the hexo, or `None` if it was placed from a seat after the
+    /// second. See [`PlacedHexo::seat`] for games of more than two players.
+    pub fn player(&self) -> Option<Player> {
+        self.seat.player()
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+/// A set of hexos of the same [`PieceSet`].
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "HexoSetRepr", into = "HexoSetRepr")]
 pub struct HexoSet {
-    bitset: u64,
+    piece_set: PieceSet,
+    bitset: u128,
+}
+
+/// The serialized form of [`HexoSet`], which is validated when deserialized.
+#[derive(Serialize, Deserialize)]
+struct HexoSetRepr {
+    #[serde(default)]
+    piece_set: PieceSet,
+    ids: Vec<usize>,
+}
+
+impl TryFrom<HexoSetRepr> for HexoSet {
+    type Error = String;
+
+    fn try_from(repr: HexoSetRepr) -> Result<Self, Self::Error> {
+        let mut hexos = Self::empty(repr.piece_set);
+        for id in repr.ids {
+            let hexo = repr
+                .piece_set
+                .hexo(id)
+                .ok_or_else(|| format!("{id} is not a valid hexo id"))?;
+            hexos.add(hexo);
+        }
+        Ok(hexos)
+    }
+}
+
+impl From<HexoSet> for HexoSetRepr {
+    fn from(hexos: HexoSet) -> Self {
+        Self {
+            piece_set: hexos.piece_set,
+            ids: hexos.iter().map(Hexo::id).collect(),
+        }
+    }
 }
 
 impl HexoSet {
-    pub fn empty() -> Self {
-        Self { bitset: 0 }
+    /// The maximum number of pieces in a [`PieceSet`].
+    pub const CAPACITY: usize = 128;
+
+    pub fn empty(piece_set: PieceSet) -> Self {
+        Self {
+            piece_set,
+            bitset: 0,
+        }
     }
 
-    pub fn all() -> Self {
+    pub fn all(piece_set: PieceSet) -> Self {
         Self {
-            bitset: (1 << N_HEXOS) - 1,
+            piece_set,
+            bitset: u128::MAX >> (Self::CAPACITY - piece_set.len()),
         }
     }
 
+    /// Creates a set from the bitset of the hexo ids, or returns `None` if there is an id out of
+    /// range.
+    pub(crate) fn from_bitset(piece_set: PieceSet, bitset: u128) -> Option<Self> {
+        let all = Self::all(piece_set);
+        (bitset & !all.bitset == 0).then_some(Self { piece_set, bitset })
+    }
+
+    pub fn piece_set(&self) -> PieceSet {
+        self.piece_set
+    }
+
     pub fn is_empty(&self) -> bool {
         self.bitset == 0
     }
 
+    pub(crate) fn bitset(&self) -> u128 {
+        self.bitset
+    }
+
+    pub fn len(&self) -> usize {
+        self.bitset.count_ones() as usize
+    }
+
+    /// Adds the hexo to the set, unless it is of another piece set.
     pub fn add(&mut self, hexo: Hexo) {
-        self.bitset |= 1u64 << hexo.id();
+        if hexo.piece_set() == self.piece_set {
+            self.bitset |= 1u128 << hexo.id();
+        }
     }
 
     pub fn remove(&mut self, hexo: Hexo) {
-        self.bitset &= !(1u64 << hexo.id());
+        if hexo.piece_set() == self.piece_set {
+            self.bitset &= !(1u128 << hexo.id());
+        }
     }
 
     /// Returns true if the collections contains a hexo.
     pub fn has(&self, hexo: Hexo) -> bool {
-        (self.bitset & (1u64 << hexo.id())) != 0
+        hexo.piece_set() == self.piece_set && (self.bitset & (1u128 << hexo.id())) != 0
     }
 
     pub fn iter(&self) -> impl Iterator<Item = Hexo> + '_ {
-        (0..N_HEXOS)
-            .map(Hexo::new)
-            .filter(move |hexo| self.has(*hexo))
+        self.piece_set.hexos().filter(move |hexo| self.has(*hexo))
+    }
+
+    /// Returns `count` hexos of the set chosen at random, or the whole set if it has fewer.
+    ///
+    /// The choice only depends on the set and the seed.
+    pub fn sample(&self, count: usize, seed: u64) -> HexoSet {
+        let mut rng = SplitMix64(seed);
+        let mut hexos: Vec<_> = self.iter().collect();
+        let count = count.min(hexos.len());
+        let mut sample = HexoSet::empty(self.piece_set);
+        for i in 0..count {
+            let j = i + (rng.next_u64() % (hexos.len() - i) as u64) as usize;
+            hexos.swap(i, j);
+            sample.add(hexos[i]);
+        }
+        sample
     }
 }
@@ -1,10 +1,37 @@
+mod bitboard;
 mod board;
+mod board_layout;
+mod board_spec;
 pub mod constants;
+mod dlx;
 mod hexo;
+mod notation;
+mod orientation;
+mod perft;
+mod piece_set;
+pub mod polyomino;
 mod pos;
+mod puzzle;
+mod random;
+mod record;
+mod region;
+mod snapshot;
 mod state;
+mod zobrist;
 
+pub use bitboard::*;
 pub use board::*;
+pub use board_layout::*;
+pub use board_spec::*;
+pub use dlx::*;
 pub use hexo::*;
+pub use notation::*;
+pub use orientation::*;
+pub use piece_set::*;
 pub use pos::*;
+pub use puzzle::*;
+pub use random::*;
+pub use record::*;
+pub use region::*;
+pub use snapshot::*;
 pub use state::*;
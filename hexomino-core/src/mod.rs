@@ -1,5 +1,20 @@
+pub mod bitboard;
 pub mod board;
+pub mod board_layout;
+pub mod board_spec;
 pub mod constants;
+pub mod dlx;
 pub mod hexo;
+pub mod notation;
+pub mod orientation;
+pub mod perft;
+pub mod piece_set;
+pub mod polyomino;
 pub mod pos;
+pub mod puzzle;
+pub mod random;
+pub mod record;
+pub mod region;
+pub mod snapshot;
 pub mod state;
+pub mod zobrist;
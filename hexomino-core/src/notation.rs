@@ -0,0 +1,360 @@
+//! A compact text notation for [`Action`]s.
+//!
+//! A pick is written as `P` followed by the hexo id, e.g. `P17`, and a ban as `B` followed by the
+//! hexo id, e.g. `B17`. A place additionally has the transform and the anchor, e.g. `P17:F2@c5`:
+//!
+//! - The transform is `R` (not flipped) or `F` (flipped), followed by the number of clockwise
+//!   rotations from 0 to 3.
+//! - The anchor is the cell the origin tile of the orientation is placed at (see
+//!   [`MovedHexo::anchor`]). It is written as the column in letters (`a`, `b`, ..., `z`, `aa`,
+//!   ...) and the row counting from 1, e.g. `c5` is `Pos::new(2, 4)`. A cell with a negative
+//!   coordinate is written as `(x,y)` instead.
+//!
+//! The hexo id is the id of the hexo in the piece set of the game, which is not written, so an
+//! action is read back with [`Action::parse_in`] and the piece set. Parsing with [`str::parse`]
+//! reads the ids as [`PieceSet::hexominoes`].
+//!
+//! Formatting an action and parsing it back in its piece set gives exactly the same action.
+
+use std::{fmt, ops::Range, str::FromStr};
+
+use super::{hexo::Transform, piece_set::PieceSet, pos::Pos, state::Action};
+use getset::{CopyGetters, Getters};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotationErrorKind {
+    #[error("expected {0}")]
+    Expected(&'static str),
+    #[error("hexo {0} does not exist")]
+    InvalidHexo(usize),
+    #[error("number is too large")]
+    NumberTooLarge,
+    #[error("unexpected trailing input")]
+    TrailingInput,
+}
+
+/// An error when parsing the notation, with the byte range of the input that is wrong.
+#[derive(Error, Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+#[error("{kind} at column {}", .span.start + 1)]
+pub struct NotationError {
+    #[getset(get_copy = "pub")]
+    kind: NotationErrorKind,
+    #[getset(get = "pub")]
+    span: Range<usize>,
+}
+
+impl NotationError {
+    /// Returns the input followed by a line that marks the wrong part, and then the error.
+    pub fn annotate(&self, input: &str) -> String {
+        let width = (self.span.end - self.span.start).max(1);
+        format!(
+            "{}\n{}{}\n{}",
+            input,
+            " ".repeat(self.span.start),
+            "^".repeat(width),
+            self
+        )
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flip = if self.index() >= 4 { 'F' } else { 'R' };
+        write!(f, "{}{}", flip, self.index() % 4)
+    }
+}
+
+/// Writes a cell in the notation, e.g. `c5`.
+pub(crate) struct Cell(pub(crate) Pos);
+
+impl Cell {
+    /// Parses a cell in the notation, e.g. `c5`.
+    pub(crate) fn parse(s: &str) -> Option<Pos> {
+        let mut parser = Parser {
+            input: s,
+            pos: 0,
+            piece_set: PieceSet::hexominoes(),
+        };
+        let cell = parser.cell().ok()?;
+        (parser.pos == s.len()).then_some(cell)
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Pos { x, y } = self.0;
+        if x < 0 || y < 0 {
+            return write!(f, "({},{})", x, y);
+        }
+        let mut letters = vec![];
+        let mut column = x as u32 + 1;
+        while column > 0 {
+            column -= 1;
+            letters.push(char::from(b'a' + (column % 26) as u8));
+            column /= 26;
+        }
+        letters.reverse();
+        write!(f, "{}{}", letters.into_iter().collect::<String>(), y + 1)
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Pick(hexo) => write!(f, "P{}", hexo.id()),
+            Action::Ban(hexo) => write!(f, "B{}", hexo.id()),
+            Action::Place(moved_hexo) => write!(
+                f,
+                "P{}:{}@{}",
+                moved_hexo.hexo().id(),
+                moved_hexo.rhexo().transform(),
+                Cell(moved_hexo.anchor())
+            ),
+        }
+    }
+}
+
+impl Action {
+    /// Parses an action in the notation, with the hexo ids of the piece set.
+    pub fn parse_in(s: &str, piece_set: PieceSet) -> Result<Self, NotationError> {
+        let mut parser = Parser {
+            input: s,
+            pos: 0,
+            piece_set,
+        };
+        let action = parser.action()?;
+        if parser.pos < s.len() {
+            return Err(parser.error(NotationErrorKind::TrailingInput, parser.pos..s.len()));
+        }
+        Ok(action)
+    }
+}
+
+impl FromStr for Action {
+    type Err = NotationError;
+
+    /// Parses an action of the standard game. Use [`Action::parse_in`] for other piece sets.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_in(s, PieceSet::hexominoes())
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    piece_set: PieceSet,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, kind: NotationErrorKind, span: Range<usize>) -> NotationError {
+        NotationError { kind, span }
+    }
+
+    fn expected(&self, what: &'static str) -> NotationError {
+        let len = self.input[self.pos..]
+            .chars()
+            .next()
+            .map_or(0, char::len_utf8);
+        self.error(NotationErrorKind::Expected(what), self.pos..self.pos + len)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let eaten = self.peek() == Some(byte);
+        if eaten {
+            self.pos += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, byte: u8, what: &'static str) -> Result<(), NotationError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.expected(what))
+        }
+    }
+
+    /// Consumes the longest run of bytes satisfying `pred`, and returns its range.
+    fn take_while(&mut self, pred: impl Fn(u8) -> bool) -> Range<usize> {
+        let start = self.pos;
+        while self.peek().map_or(false, &pred) {
+            self.pos += 1;
+        }
+        start..self.pos
+    }
+
+    fn number(&mut self, what: &'static str) -> Result<(usize, Range<usize>), NotationError> {
+        let span = self.take_while(|byte| byte.is_ascii_digit());
+        if span.is_empty() {
+            return Err(self.expected(what));
+        }
+        let number = self.input[span.clone()]
+            .parse()
+            .map_err(|_| self.error(NotationErrorKind::NumberTooLarge, span.clone()))?;
+        Ok((number, span))
+    }
+
+    fn signed_number(&mut self) -> Result<i32, NotationError> {
+        let start = self.pos;
+        let negative = self.eat(b'-');
+        let (number, span) = self.number("a number")?;
+        let number = i32::try_from(number)
+            .map_err(|_| self.error(NotationErrorKind::NumberTooLarge, start..span.end))?;
+        Ok(if negative { -number } else { number })
+    }
+
+    fn action(&mut self) -> Result<Action, NotationError> {
+        let ban = self.eat(b'B');
+        if !ban {
+            self.expect(b'P', "`P` or `B`")?;
+        }
+        let (id, span) = self.number("a hexo id")?;
+        let hexo = self
+            .piece_set
+            .hexo(id)
+            .ok_or_else(|| self.error(NotationErrorKind::InvalidHexo(id), span))?;
+        if ban {
+            return Ok(Action::Ban(hexo));
+        }
+        if self.pos == self.input.len() {
+            return Ok(Action::Pick(hexo));
+        }
+        self.expect(b':', "`:` or the end")?;
+        let transform = self.transform()?;
+        self.expect(b'@', "`@`")?;
+        let anchor = self.cell()?;
+        Ok(Action::Place(hexo.apply(transform).place_at(anchor)))
+    }
+
+    fn transform(&mut self) -> Result<Transform, NotationError> {
+        let flipped = match self.peek() {
+            Some(b'R') => false,
+            Some(b'F') => true,
+            _ => return Err(self.expected("`R` or `F`")),
+        };
+        self.pos += 1;
+        let rotate = match self.peek() {
+            Some(byte @ b'0'..=b'3') => byte - b'0',
+            _ => return Err(self.expected("a rotation from 0 to 3")),
+        };
+        self.pos += 1;
+        Ok(Transform::new(flipped, rotate as i32))
+    }
+
+    fn cell(&mut self) -> Result<Pos, NotationError> {
+        if self.eat(b'(') {
+            let x = self.signed_number()?;
+            self.expect(b',', "`,`")?;
+            let y = self.signed_number()?;
+            self.expect(b')', "`)`")?;
+            return Ok(Pos::new(x, y));
+        }
+        let span = self.take_while(|byte| byte.is_ascii_lowercase());
+        if span.is_empty() {
+            return Err(self.expected("a column letter or `(`"));
+        }
+        let column = self.input[span.clone()]
+            .bytes()
+            .try_fold(0i32, |column, byte| {
+                column
+                    .checked_mul(26)?
+                    .checked_add((byte - b'a') as i32 + 1)
+            })
+            .ok_or_else(|| self.error(NotationErrorKind::NumberTooLarge, span))?;
+        let start = self.pos;
+        let (row, span) = self.number("a row number")?;
+        let row = i32::try_from(row)
+            .ok()
+            .filter(|row| *row > 0)
+            .ok_or_else(|| {
+                self.error(NotationErrorKind::Expected("a row from 1"), start..span.end)
+            })?;
+        Ok(Pos::new(column - 1, row - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Hexo;
+    use assert2::{check, let_assert};
+
+    #[test]
+    fn notation_round_trips() {
+        for hexo in Hexo::all_hexos() {
+            check!(Action::Pick(hexo).to_string().parse() == Ok(Action::Pick(hexo)));
+            check!(Action::Ban(hexo).to_string().parse() == Ok(Action::Ban(hexo)));
+            for transform in Transform::all() {
+                for anchor in [
+                    Pos::new(2, 4),
+                    Pos::new(27, 0),
+                    Pos::new(-1, 3),
+                    Pos::new(0, -2),
+                ] {
+                    let moved_hexo = hexo.apply(transform).place_at(anchor);
+                    let_assert!(
+                        Ok(Action::Place(parsed)) = Action::Place(moved_hexo).to_string().parse()
+                    );
+                    check!(parsed.rhexo().transform() == transform);
+                    check!(parsed.displacement() == moved_hexo.displacement());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn notation_round_trips_in_the_piece_set() {
+        let pentominoes = PieceSet::pentominoes();
+        for hexo in pentominoes.hexos() {
+            let moved_hexo = hexo.apply(Transform::new(true, 3)).place_at(Pos::new(4, 2));
+            for action in [
+                Action::Pick(hexo),
+                Action::Ban(hexo),
+                Action::Place(moved_hexo),
+            ] {
+                check!(Action::parse_in(&action.to_string(), pentominoes) == Ok(action));
+                check!(action.to_string().parse::<Action>() != Ok(action));
+            }
+        }
+        check!(
+            Action::parse_in("P12", pentominoes).unwrap_err().kind()
+                == NotationErrorKind::InvalidHexo(12)
+        );
+    }
+
+    #[test]
+    fn notation_formats_cells() {
+        let action = Action::Place(
+            Hexo::new(1)
+                .apply(Transform::new(true, 2))
+                .place_at(Pos::new(2, 4)),
+        );
+        check!(action.to_string() == "P1:F2@c5");
+        let action = Action::Place(Hexo::new(1).apply(Transform::I).place_at(Pos::new(26, 0)));
+        check!(action.to_string() == "P1:R0@aa1");
+        let action = Action::Place(Hexo::new(0).apply(Transform::I).place_at(Pos::new(-1, 0)));
+        check!(action.to_string() == "P0:R0@(-1,0)");
+    }
+
+    #[test]
+    fn notation_errors_point_to_the_bad_part() {
+        let error = |s: &str| s.parse::<Action>().unwrap_err();
+        check!(error("Q1").span() == &(0..1));
+        check!(error("P").kind() == NotationErrorKind::Expected("a hexo id"));
+        check!(error("P9").kind() == NotationErrorKind::InvalidHexo(9));
+        check!(error("P1:X2@c5").span() == &(3..4));
+        check!(error("P1:F5@c5").span() == &(4..5));
+        check!(error("P1:F2c5").span() == &(5..6));
+        check!(error("P1:F2@c0").span() == &(7..8));
+        check!(error("P1:F2@c5x").kind() == NotationErrorKind::TrailingInput);
+        check!(
+            error("P1:F2@c5x").annotate("P1:F2@c5x")
+                == "P1:F2@c5x\n        ^\nunexpected trailing input at column 9"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use super::{
+    hexo::{Hexo, MovedHexo, RHexo, Tiles, Transform},
+    pos::Pos,
+};
+use std::hash::{Hash, Hasher};
+
+/// One of the distinct shapes a hexo can take after being flipped and rotated.
+///
+/// The tiles are sorted, and translated so that the smallest tile is at the origin. Hence the
+/// origin is always one of the tiles.
+#[derive(Debug, Clone)]
+pub struct Orientation {
+    transform: Transform,
+    tiles: Tiles,
+    offset: Pos,
+    symmetry: SymmetryGroup,
+}
+
+impl Orientation {
+    /// The canonical transform that produces this orientation.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// The normalized tiles of this orientation.
+    pub fn tiles(&self) -> &[Pos] {
+        &self.tiles
+    }
+
+    /// The transforms that map this orientation to itself.
+    pub fn symmetry(&self) -> SymmetryGroup {
+        self.symmetry
+    }
+
+    /// Returns the placement of `hexo` in this orientation that puts the origin tile on `anchor`.
+    pub fn place_at(&self, hexo: Hexo, anchor: Pos) -> MovedHexo {
+        hexo.apply(self.transform).move_to(anchor + self.offset)
+    }
+}
+
+/// A subgroup of the eight transforms of the square.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymmetryGroup {
+    bitset: u8,
+}
+
+impl SymmetryGroup {
+    pub fn contains(self, transform: Transform) -> bool {
+        self.bitset & (1 << transform.index()) != 0
+    }
+
+    /// Returns the number of transforms in the group.
+    pub fn order(self) -> usize {
+        self.bitset.count_ones() as usize
+    }
+
+    pub fn transforms(self) -> impl Iterator<Item = Transform> {
+        Transform::all().filter(move |transform| self.contains(*transform))
+    }
+}
+
+impl std::fmt::Debug for SymmetryGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.transforms()).finish()
+    }
+}
+
+pub(crate) struct HexoOrientations {
+    orientations: Vec<Orientation>,
+    /// The index of the orientation each transform produces.
+    orientation_of: [usize; 8],
+    /// The translation that normalizes the tiles after each transform.
+    offset_of: [Pos; 8],
+}
+
+/// Sorts the tiles and translates them so that the smallest tile is at the origin.
+fn normalize(mut tiles: Tiles) -> (Tiles, Pos) {
+    tiles.sort();
+    let origin = tiles[0];
+    let offset = Pos::new(-origin.x, -origin.y);
+    (
+        tiles.into_iter().map(|tile| tile + offset).collect(),
+        offset,
+    )
+}
+
+fn transform_tiles(tiles: &[Pos], transform: Transform) -> Tiles {
+    tiles.iter().map(|tile| transform.apply_on(*tile)).collect()
+}
+
+impl HexoOrientations {
+    /// Computes the orientations of a piece with the tiles.
+    pub(crate) fn new(piece_tiles: &[Pos]) -> Self {
+        let mut orientations: Vec<Orientation> = vec![];
+        let mut orientation_of = [0; 8];
+        let mut offset_of = [Pos::ZERO; 8];
+        for transform in Transform::all() {
+            let (tiles, offset) = normalize(transform_tiles(piece_tiles, transform));
+            let idx = match orientations.iter().position(|o| o.tiles == tiles) {
+                Some(idx) => idx,
+                None => {
+                    let symmetry = SymmetryGroup {
+                        bitset: Transform::all()
+                            .filter(|g| normalize(transform_tiles(&tiles, *g)).0 == tiles)
+                            .fold(0, |bitset, g| bitset | (1 << g.index())),
+                    };
+                    orientations.push(Orientation {
+                        transform,
+                        tiles,
+                        offset,
+                        symmetry,
+                    });
+                    orientations.len() - 1
+                }
+            };
+            orientation_of[transform.index()] = idx;
+            offset_of[transform.index()] = offset;
+        }
+        Self {
+            orientations,
+            orientation_of,
+            offset_of,
+        }
+    }
+}
+
+impl Hexo {
+    /// Returns the distinct orientations of the hexo, in the order of [`Transform::all`].
+    pub fn orientations(self) -> &'static [Orientation] {
+        &self.piece_set().piece(self.id()).orientations.orientations
+    }
+
+    /// Returns the transforms that map the hexo to itself.
+    pub fn symmetry(self) -> SymmetryGroup {
+        self.orientations()[0].symmetry
+    }
+}
+
+impl RHexo {
+    fn orientations(&self) -> &'static HexoOrientations {
+        let hexo = self.hexo();
+        &hexo.piece_set().piece(hexo.id()).orientations
+    }
+
+    pub(crate) fn orientation_idx(&self) -> usize {
+        self.orientations().orientation_of[self.transform().index()]
+    }
+
+    fn offset(&self) -> Pos {
+        self.orientations().offset_of[self.transform().index()]
+    }
+
+    /// Returns the orientation this transformed hexo has.
+    pub fn orientation(&self) -> &'static Orientation {
+        &self.hexo().orientations()[self.orientation_idx()]
+    }
+
+    /// Returns the placement that puts the origin tile of its orientation on `anchor`.
+    ///
+    /// This is the inverse of [`MovedHexo::anchor`].
+    pub fn place_at(self, anchor: Pos) -> MovedHexo {
+        self.move_to(anchor + self.offset())
+    }
+
+    /// Returns the transformed hexo with the canonical transform of its orientation.
+    ///
+    /// Two transformed hexos have the same shape if and only if their canonical forms are equal.
+    pub fn canonical(self) -> RHexo {
+        self.hexo().apply(self.orientation().transform)
+    }
+}
+
+impl PartialEq for RHexo {
+    fn eq(&self, other: &Self) -> bool {
+        self.hexo() == other.hexo() && self.orientation_idx() == other.orientation_idx()
+    }
+}
+
+impl Eq for RHexo {}
+
+impl Hash for RHexo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hexo().hash(state);
+        self.orientation_idx().hash(state);
+    }
+}
+
+impl MovedHexo {
+    /// Returns the position the origin tile of the orientation is placed at.
+    pub fn anchor(&self) -> Pos {
+        let offset = self.rhexo().offset();
+        Pos::new(
+            self.displacement().x - offset.x,
+            self.displacement().y - offset.y,
+        )
+    }
+
+    /// Returns the placement with the canonical transform that covers the same tiles.
+    pub fn canonical(self) -> MovedHexo {
+        self.rhexo()
+            .orientation()
+            .place_at(self.hexo(), self.anchor())
+    }
+}
+
+impl PartialEq for MovedHexo {
+    fn eq(&self, other: &Self) -> bool {
+        self.rhexo() == other.rhexo() && self.anchor() == other.anchor()
+    }
+}
+
+impl Eq for MovedHexo {}
+
+impl Hash for MovedHexo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rhexo().hash(state);
+        self.anchor().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use itertools::Itertools;
+
+    #[test]
+    fn orientations_are_distinct_and_normalized() {
+        for hexo in Hexo::all_hexos() {
+            let orientations = hexo.orientations();
+            check!(orientations.len() * hexo.symmetry().order() == 8);
+            check!(orientations.iter().map(|o| &o.tiles).all_unique());
+            for orientation in orientations {
+                check!(orientation.tiles[0] == Pos::ZERO);
+                check!(orientation.tiles.windows(2).all(|w| w[0] < w[1]));
+                check!(orientation.symmetry.order() == hexo.symmetry().order());
+            }
+        }
+        check!(Hexo::new(0).orientations().len() == 8);
+        check!(Hexo::new(1).orientations().len() == 2);
+        check!(Hexo::new(1).symmetry().order() == 4);
+    }
+
+    #[test]
+    fn placements_covering_same_tiles_are_equal() {
+        let hexo = Hexo::new(1);
+        let standing = hexo.apply(Transform::I).move_to(Pos::new(2, 0));
+        let upside_down = hexo.apply(Transform::new(false, 2)).move_to(Pos::new(2, 5));
+        let flipped = hexo.apply(Transform::new(true, 0)).move_to(Pos::new(2, 0));
+        let lying = hexo.apply(Transform::new(false, 1)).move_to(Pos::new(2, 0));
+        check!(standing == upside_down);
+        check!(standing == flipped);
+        check!(standing != lying);
+        check!(upside_down.canonical().rhexo().transform() == Transform::I);
+        check!(upside_down.canonical().displacement() == Pos::new(2, 0));
+        check!(
+            upside_down.tiles().sorted().collect_vec()
+                == upside_down.canonical().tiles().sorted().collect_vec()
+        );
+    }
+
+    #[test]
+    fn place_at_puts_origin_tile_on_anchor() {
+        for hexo in Hexo::all_hexos() {
+            for orientation in hexo.orientations() {
+                let moved_hexo = orientation.place_at(hexo, Pos::new(3, 1));
+                check!(moved_hexo.anchor() == Pos::new(3, 1));
+                check!(
+                    moved_hexo.tiles().sorted().collect_vec()
+                        == orientation
+                            .tiles
+                            .iter()
+                            .map(|tile| tile + Pos::new(3, 1))
+                            .collect_vec()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Move path enumeration, which counts the leaves of the game tree to a fixed depth.
+//!
+//! The counts only depend on the rules and the move generator, so comparing them against known
+//! numbers catches changes to either, and timing them measures the move generator.
+
+use super::state::{Action, State};
+
+impl State {
+    /// Returns the number of sequences of `depth` legal actions from the state.
+    ///
+    /// A sequence that ends the game early is not counted, so the count at depth 1 is the number
+    /// of legal actions. The state is left as it was, including the actions to redo.
+    pub fn perft(&mut self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let actions: Vec<_> = self.legal_actions().collect();
+        if depth == 1 {
+            return actions.len() as u64;
+        }
+        actions
+            .into_iter()
+            .map(|action| {
+                self.apply(action).unwrap();
+                let count = self.perft(depth - 1);
+                self.unapply();
+                count
+            })
+            .sum()
+    }
+
+    /// Returns [`State::perft`] of every legal action at `depth - 1`, which shows where two
+    /// counts diverge.
+    pub fn perft_divide(&mut self, depth: usize) -> Vec<(Action, u64)> {
+        let actions: Vec<_> = self.legal_actions().collect();
+        actions
+            .into_iter()
+            .map(|action| {
+                self.apply(action).unwrap();
+                let count = self.perft(depth.saturating_sub(1));
+                self.unapply();
+                (action, count)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{BoardSpec, HexoSet, PieceSet, RandomPlayer};
+
+    /// A game of the pentominoes on a 6x10 board played at random until `num_actions` actions
+    /// have been played. The first 12 actions are the draft.
+    fn pentomino_position(seed: u64, num_actions: usize) -> State {
+        let mut state = State::with_pool(
+            BoardSpec::new(6, 10).unwrap(),
+            HexoSet::all(PieceSet::pentominoes()),
+        )
+        .unwrap();
+        RandomPlayer::new(seed).play_until(&mut state, num_actions);
+        state
+    }
+
+    #[test]
+    fn perft_matches_golden_numbers() {
+        let golden: [(u64, usize, &[u64]); 4] = [
+            (1, 12, &[1192, 720164]),
+            (2, 12, &[1144, 729168]),
+            (1, 14, &[231, 30286, 2284766]),
+            (2, 16, &[112, 4017, 98902]),
+        ];
+        for (seed, num_actions, counts) in golden {
+            let mut state = pentomino_position(seed, num_actions);
+            let actions: Vec<_> = state.actions().collect();
+            for (depth, &count) in counts.iter().enumerate() {
+                let depth = depth + 1;
+                check!(
+                    state.perft(depth) == count,
+                    "seed {seed}, {num_actions} actions, depth {depth}"
+                );
+            }
+            check!(state.actions().collect::<Vec<_>>() == actions);
+        }
+    }
+
+    #[test]
+    fn perft_divide_sums_to_perft() {
+        let mut state = pentomino_position(2, 16);
+        let divide = state.perft_divide(3);
+        check!(divide.len() == 112);
+        check!(divide.iter().map(|&(_, count)| count).sum::<u64>() == state.perft(3));
+        check!(state.perft(0) == 1);
+    }
+
+    #[test]
+    fn perft_keeps_the_actions_to_redo() {
+        let mut state = pentomino_position(2, 16);
+        let last = state.undo();
+        let count = state.perft(2);
+        check!(
+            state
+                .perft_divide(2)
+                .iter()
+                .map(|&(_, count)| count)
+                .sum::<u64>()
+                == count
+        );
+        check!(state.can_redo());
+        check!(state.redo() == last);
+    }
+
+    #[test]
+    fn perft_is_zero_after_the_game_ends() {
+        let mut state = pentomino_position(1, 20);
+        check!(state.current_player().is_none());
+        check!(state.perft(1) == 0);
+        check!(state.perft(0) == 1);
+    }
+}
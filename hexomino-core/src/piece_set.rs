@@ -0,0 +1,412 @@
+use std::{
+    collections::BTreeSet,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Mutex,
+};
+
+use super::{
+    board_spec::BoardSpec,
+    constants::HEXOS,
+    hexo::{Hexo, HexoSet, Tiles},
+    orientation::HexoOrientations,
+    polyomino,
+    pos::Pos,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PieceSetError {
+    #[error("a piece set must have at least one piece")]
+    Empty,
+    #[error(
+        "a piece set can have at most {} pieces, but got {0}",
+        HexoSet::CAPACITY
+    )]
+    TooManyPieces(usize),
+    #[error("piece {0} has no tile")]
+    EmptyPiece(usize),
+    #[error("piece {0} has more than {} tiles", PieceSet::MAX_TILES)]
+    TooManyTiles(usize),
+    #[error(
+        "piece {0} has a tile with a coordinate beyond {}",
+        PieceSet::MAX_COORDINATE
+    )]
+    TileOutOfRange(usize),
+    #[error("piece {0} has a tile more than once")]
+    DuplicateTile(usize),
+    #[error("the tiles of piece {0} are not connected")]
+    Disconnected(usize),
+    #[error("piece {1} has the same shape as piece {0}")]
+    DuplicatePiece(usize, usize),
+    #[error(
+        "no more than {} piece sets can be registered",
+        PieceSet::MAX_REGISTERED
+    )]
+    TooManySets,
+}
+
+/// A piece of a piece set, with everything derived from its tiles.
+pub(crate) struct Piece {
+    pub(crate) tiles: Tiles,
+    pub(crate) borders: Vec<(Pos, Pos)>,
+    pub(crate) orientations: HexoOrientations,
+}
+
+impl Piece {
+    fn new(tiles: Tiles) -> Self {
+        let cells: Vec<_> = tiles.iter().map(|tile| (tile.x, tile.y)).collect();
+        Self {
+            borders: polyomino::borders(&cells)
+                .into_iter()
+                .map(|(p1, p2)| (p1.into(), p2.into()))
+                .collect(),
+            orientations: HexoOrientations::new(&tiles),
+            tiles,
+        }
+    }
+}
+
+pub(crate) struct PieceSetDef {
+    /// The position in the registry, which identifies the set within the process.
+    index: usize,
+    pub(crate) pieces: Vec<Piece>,
+}
+
+/// The pieces a game is played with, e.g. the hexominoes of the standard game.
+///
+/// A piece set is registered once and lives for the rest of the program, so it is cheap to copy
+/// and compare. Registering the same pieces again returns the same set.
+///
+/// The built-in sets always have the same [`PieceSet::index`], while other sets are numbered in
+/// the order they are registered. A built-in set is serialized as its index, and any other set
+/// as the tiles of its pieces, which registers it when deserialized.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "PieceSetRepr", into = "PieceSetRepr")]
+pub struct PieceSet(&'static PieceSetDef);
+
+/// The serialized form of [`PieceSet`].
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PieceSetRepr {
+    BuiltIn(usize),
+    Custom(Vec<Tiles>),
+}
+
+fn leak_def(index: usize, pieces: Vec<Tiles>) -> &'static PieceSetDef {
+    Box::leak(Box::new(PieceSetDef {
+        index,
+        pieces: pieces.into_iter().map(Piece::new).collect(),
+    }))
+}
+
+static HEXOMINOES: Lazy<&'static PieceSetDef> =
+    Lazy::new(|| leak_def(0, HEXOS.iter().map(|hexo| hexo.tiles.to_vec()).collect()));
+
+static PENTOMINOES: Lazy<&'static PieceSetDef> = Lazy::new(|| {
+    let pentominoes = polyomino::free_polyominoes(5)
+        .into_iter()
+        .map(|cells| cells.into_iter().map(Pos::from).collect())
+        .collect();
+    leak_def(1, pentominoes)
+});
+
+/// All the registered piece sets, indexed by [`PieceSet::index`].
+static REGISTRY: Lazy<Mutex<Vec<&'static PieceSetDef>>> =
+    Lazy::new(|| Mutex::new(vec![*HEXOMINOES, *PENTOMINOES]));
+
+impl PieceSet {
+    /// The most piece sets that can be registered, including the built-in ones.
+    ///
+    /// Registered sets are never freed, so this bounds the memory that sets read from untrusted
+    /// data can take.
+    pub const MAX_REGISTERED: usize = 64;
+
+    /// The most tiles a piece can have.
+    pub const MAX_TILES: usize = u8::MAX as usize;
+
+    /// The largest absolute value of a coordinate of a tile of a piece. Any piece that fits on a
+    /// board can be given within it.
+    pub const MAX_COORDINATE: i32 = BoardSpec::MAX_SIDE as i32;
+
+    /// The hexominoes of the standard game, which are all the free hexominoes except the
+    /// straight one.
+    pub fn hexominoes() -> Self {
+        Self(*HEXOMINOES)
+    }
+
+    /// The 12 free pentominoes.
+    pub fn pentominoes() -> Self {
+        Self(*PENTOMINOES)
+    }
+
+    /// Registers a piece set, with the pieces numbered in order.
+    ///
+    /// Every piece must be a non-empty set of at most [`PieceSet::MAX_TILES`] connected tiles
+    /// with coordinates within [`PieceSet::MAX_COORDINATE`], and no two pieces can have the same
+    /// shape up to rotation and reflection. The tiles of a piece are kept as given, so they
+    /// determine how [`Transform`](crate::Transform)s and displacements move the piece.
+    pub fn new(pieces: impl IntoIterator<Item = Tiles>) -> Result<Self, PieceSetError> {
+        let pieces: Vec<Tiles> = pieces.into_iter().collect();
+        if pieces.is_empty() {
+            return Err(PieceSetError::Empty);
+        }
+        if pieces.len() > HexoSet::CAPACITY {
+            return Err(PieceSetError::TooManyPieces(pieces.len()));
+        }
+        let mut shapes = vec![];
+        for (id, tiles) in pieces.iter().enumerate() {
+            if tiles.is_empty() {
+                return Err(PieceSetError::EmptyPiece(id));
+            }
+            if tiles.len() > Self::MAX_TILES {
+                return Err(PieceSetError::TooManyTiles(id));
+            }
+            let range = -Self::MAX_COORDINATE..=Self::MAX_COORDINATE;
+            let in_range = |coordinate| range.contains(&coordinate);
+            if !tiles
+                .iter()
+                .all(|tile| in_range(tile.x) && in_range(tile.y))
+            {
+                return Err(PieceSetError::TileOutOfRange(id));
+            }
+            let cells: BTreeSet<_> = tiles.iter().map(|tile| (tile.x, tile.y)).collect();
+            if cells.len() != tiles.len() {
+                return Err(PieceSetError::DuplicateTile(id));
+            }
+            if !is_connected(&cells) {
+                return Err(PieceSetError::Disconnected(id));
+            }
+            let shape = polyomino::normalize(&cells.into_iter().collect::<Vec<_>>());
+            if let Some(other) = shapes.iter().position(|other| *other == shape) {
+                return Err(PieceSetError::DuplicatePiece(other, id));
+            }
+            shapes.push(shape);
+        }
+
+        let mut registry = REGISTRY.lock().unwrap();
+        let existing = registry.iter().find(|def| {
+            def.pieces.len() == pieces.len()
+                && def
+                    .pieces
+                    .iter()
+                    .zip(&pieces)
+                    .all(|(piece, tiles)| piece.tiles == *tiles)
+        });
+        if let Some(def) = existing {
+            return Ok(Self(def));
+        }
+        if registry.len() >= Self::MAX_REGISTERED {
+            return Err(PieceSetError::TooManySets);
+        }
+        let def = leak_def(registry.len(), pieces);
+        registry.push(def);
+        Ok(Self(def))
+    }
+
+    /// Returns the set registered with the index, if there is any.
+    pub fn from_index(index: usize) -> Option<Self> {
+        REGISTRY.lock().unwrap().get(index).copied().map(Self)
+    }
+
+    /// The number that identifies the set within the process.
+    pub fn index(self) -> usize {
+        self.0.index
+    }
+
+    /// Returns true if the set is [`PieceSet::hexominoes`] or [`PieceSet::pentominoes`], whose
+    /// indices are the same in every process.
+    pub fn is_built_in(self) -> bool {
+        self.index() < 2
+    }
+
+    /// Returns the number of pieces in the set.
+    pub fn len(self) -> usize {
+        self.0.pieces.len()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the piece with the id, or `None` if the id is out of range.
+    pub fn hexo(self, id: usize) -> Option<Hexo> {
+        (id < self.len()).then(|| Hexo::from_parts(self, id))
+    }
+
+    /// Returns all the pieces in the order of their ids.
+    pub fn hexos(self) -> impl Iterator<Item = Hexo> {
+        (0..self.len()).map(move |id| Hexo::from_parts(self, id))
+    }
+
+    pub(crate) fn piece(self, id: usize) -> &'static Piece {
+        &self.0.pieces[id]
+    }
+}
+
+/// Returns true if the cells are connected through their edges.
+fn is_connected(cells: &BTreeSet<(i32, i32)>) -> bool {
+    let start = match cells.iter().next() {
+        Some(&start) => start,
+        None => return true,
+    };
+    let mut visited = BTreeSet::from([start]);
+    let mut stack = vec![start];
+    while let Some((x, y)) = stack.pop() {
+        for neighbor in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            if cells.contains(&neighbor) && visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+    visited.len() == cells.len()
+}
+
+impl Default for PieceSet {
+    fn default() -> Self {
+        Self::hexominoes()
+    }
+}
+
+impl PartialEq for PieceSet {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for PieceSet {}
+
+impl Hash for PieceSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index().hash(state);
+    }
+}
+
+impl fmt::Debug for PieceSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PieceSet").field(&self.index()).finish()
+    }
+}
+
+impl TryFrom<PieceSetRepr> for PieceSet {
+    type Error = String;
+
+    fn try_from(repr: PieceSetRepr) -> Result<Self, Self::Error> {
+        match repr {
+            PieceSetRepr::BuiltIn(index) => Self::from_index(index)
+                .filter(|piece_set| piece_set.is_built_in())
+                .ok_or_else(|| format!("{index} is not a built-in piece set")),
+            PieceSetRepr::Custom(pieces) => Self::new(pieces).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+impl From<PieceSet> for PieceSetRepr {
+    fn from(piece_set: PieceSet) -> Self {
+        if piece_set.is_built_in() {
+            Self::BuiltIn(piece_set.index())
+        } else {
+            Self::Custom(
+                piece_set
+                    .hexos()
+                    .map(|hexo| hexo.tiles().collect())
+                    .collect(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{polyomino::Cell, BoardSpec, State};
+    use assert2::{check, let_assert};
+
+    fn tiles(cells: &[Cell]) -> Tiles {
+        cells.iter().copied().map(Pos::from).collect()
+    }
+
+    #[test]
+    fn built_in_sets_have_fixed_indices() {
+        check!(PieceSet::hexominoes().index() == 0);
+        check!(PieceSet::pentominoes().index() == 1);
+        check!(PieceSet::pentominoes().len() == 12);
+        check!(PieceSet::from_index(1) == Some(PieceSet::pentominoes()));
+        check!(PieceSet::default() == PieceSet::hexominoes());
+    }
+
+    #[test]
+    fn registering_same_pieces_returns_same_set() {
+        let pieces = vec![tiles(&[(0, 0), (0, 1)]), tiles(&[(0, 0), (0, 1), (1, 0)])];
+        let_assert!(Ok(set) = PieceSet::new(pieces.clone()));
+        check!(PieceSet::new(pieces) == Ok(set));
+        check!(set.hexos().map(|hexo| hexo.tiles().count()).eq([2, 3]));
+        check!(set.index() >= 2);
+    }
+
+    #[test]
+    fn custom_sets_are_serialized_by_their_pieces() {
+        let pieces = vec![tiles(&[(0, 0), (1, 0)]), tiles(&[(0, 0), (1, 0), (1, 1)])];
+        let_assert!(Ok(set) = PieceSet::new(pieces.clone()));
+        let json = serde_json::to_value(set).unwrap();
+        check!(json == serde_json::to_value(&pieces).unwrap());
+        check!(serde_json::from_value::<PieceSet>(json).ok() == Some(set));
+        check!(serde_json::to_string(&PieceSet::pentominoes()).unwrap() == "1");
+        check!(serde_json::from_str::<PieceSet>(&set.index().to_string()).is_err());
+
+        let hexo = set.hexo(1).unwrap();
+        let json = serde_json::to_string(&hexo).unwrap();
+        check!(serde_json::from_str::<Hexo>(&json).ok() == Some(hexo));
+        check!(serde_json::to_string(&Hexo::new(1)).unwrap() == "1");
+        check!(serde_json::from_str::<Hexo>(&(set.index() << 8).to_string()).is_err());
+    }
+
+    #[test]
+    fn invalid_piece_sets_are_rejected() {
+        check!(PieceSet::new(vec![]) == Err(PieceSetError::Empty));
+        check!(PieceSet::new(vec![vec![]]) == Err(PieceSetError::EmptyPiece(0)));
+        check!(
+            PieceSet::new(vec![tiles(&[(0, 0), (0, 0)])]) == Err(PieceSetError::DuplicateTile(0))
+        );
+        check!(
+            PieceSet::new(vec![tiles(&[(0, 0)]), tiles(&[(0, 0), (1, 1)])])
+                == Err(PieceSetError::Disconnected(1))
+        );
+        check!(
+            PieceSet::new(vec![tiles(&[(0, 0), (0, 1)]), tiles(&[(3, 3), (4, 3)])])
+                == Err(PieceSetError::DuplicatePiece(0, 1))
+        );
+        let line = (0..=PieceSet::MAX_TILES as i32)
+            .map(|x| (x, 0))
+            .collect::<Vec<_>>();
+        check!(PieceSet::new(vec![tiles(&line)]) == Err(PieceSetError::TooManyTiles(0)));
+    }
+
+    #[test]
+    fn extreme_coordinates_are_rejected() {
+        for (x, y) in [(i32::MAX, 0), (i32::MIN, 0), (0, 40000), (-256, 0)] {
+            let piece = tiles(&[(x, y), (x - x.signum(), y - y.signum())]);
+            check!(PieceSet::new(vec![piece]) == Err(PieceSetError::TileOutOfRange(0)));
+        }
+        let json = r#"[[{"x":2147483647,"y":0},{"x":2147483646,"y":0}]]"#;
+        check!(serde_json::from_str::<PieceSet>(json).is_err());
+        let max = PieceSet::MAX_COORDINATE;
+        let_assert!(Ok(set) = PieceSet::new(vec![tiles(&[(-max, max), (-max, max - 1)])]));
+        let state = State::with_piece_set(BoardSpec::new(4, 6).unwrap(), set);
+        let_assert!(Ok(restored) = crate::Snapshot::from_bytes(&state.snapshot().to_bytes()));
+        check!(restored == state.snapshot());
+    }
+
+    #[test]
+    fn game_is_played_with_the_chosen_set() {
+        let pentominoes = PieceSet::pentominoes();
+        let mut state = State::with_piece_set(BoardSpec::new(5, 6).unwrap(), pentominoes);
+        check!(state.legal_actions().count() == 12);
+        let hexo = pentominoes.hexo(0).unwrap();
+        check!(state.current_player_play(crate::Action::Pick(hexo)) == Ok(()));
+        let standard = crate::Action::Pick(Hexo::new(1));
+        check!(state.current_player_play(standard).is_err());
+    }
+}
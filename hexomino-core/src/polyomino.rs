@@ -0,0 +1,199 @@
+//! Enumeration of free polyominoes.
+//!
+//! This module is also included by the build script to generate [`crate::constants::HEXOS`], so
+//! it only depends on `std`.
+
+use std::collections::BTreeSet;
+
+/// A cell of a polyomino, as `(x, y)`.
+pub type Cell = (i32, i32);
+
+fn reflect((x, y): Cell) -> Cell {
+    (-x, y)
+}
+
+fn rotate((x, y): Cell) -> Cell {
+    (-y, x)
+}
+
+/// Sorts the cells and translates them so that the smallest cell is at the origin.
+fn minimal_repr(cells: &[Cell]) -> Vec<Cell> {
+    let mut cells = cells.to_vec();
+    cells.sort_unstable();
+    let (ox, oy) = cells[0];
+    cells.iter().map(|(x, y)| (x - ox, y - oy)).collect()
+}
+
+/// Returns the canonical form of a polyomino, which is the smallest [`minimal_repr`] among all
+/// its rotations and reflections.
+pub fn normalize(cells: &[Cell]) -> Vec<Cell> {
+    let mut cells = cells.to_vec();
+    let mut min_repr = minimal_repr(&cells);
+    for _ in 0..4 {
+        let reflected: Vec<_> = cells.iter().copied().map(reflect).collect();
+        min_repr = min_repr
+            .min(minimal_repr(&cells))
+            .min(minimal_repr(&reflected));
+        cells = cells.into_iter().map(rotate).collect();
+    }
+    min_repr
+}
+
+/// Returns the canonical forms of all the free polyominoes with `n` cells, in sorted order.
+pub fn free_polyominoes(n: usize) -> Vec<Vec<Cell>> {
+    assert!(n > 0);
+    let mut polys = BTreeSet::from([vec![(0, 0)]]);
+    for _ in 1..n {
+        let mut next = BTreeSet::new();
+        for poly in &polys {
+            for &(x, y) in poly {
+                for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                    let cell = (x + dx, y + dy);
+                    if poly.contains(&cell) {
+                        continue;
+                    }
+                    let mut grown = poly.clone();
+                    grown.push(cell);
+                    next.insert(normalize(&grown));
+                }
+            }
+        }
+        polys = next;
+    }
+    polys.into_iter().collect()
+}
+
+/// Returns the unit edges on the outline of a polyomino, in the order they are found.
+///
+/// Each edge is given by its two end points, with the larger one first.
+pub fn borders(cells: &[Cell]) -> Vec<(Cell, Cell)> {
+    const CORNERS: [Cell; 4] = [(0, 0), (0, 1), (1, 1), (1, 0)];
+    let mut edges: Vec<((Cell, Cell), usize)> = vec![];
+    for &(x, y) in cells {
+        for i in 0..4 {
+            let (d1, d2) = (CORNERS[i], CORNERS[(i + 1) % 4]);
+            let (d1, d2) = if d1 < d2 { (d2, d1) } else { (d1, d2) };
+            let edge = ((x + d1.0, y + d1.1), (x + d2.0, y + d2.1));
+            match edges.iter_mut().find(|(e, _)| *e == edge) {
+                Some((_, count)) => *count += 1,
+                None => edges.push((edge, 1)),
+            }
+        }
+    }
+    edges
+        .into_iter()
+        .filter(|(_, count)| *count == 1)
+        .map(|(edge, _)| edge)
+        .collect()
+}
+
+/// The canonical forms of the hexos in the game, in the order of their ids.
+///
+/// These are the 35 free hexominoes except the straight one. The ids are fixed by this table,
+/// since they are stored in game records.
+pub const HEXOS: [[Cell; 6]; 34] = [
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 1), (1, 2)],
+    [(0, 0), (0, 1), (0, 2), (1, -1), (1, 0), (1, 1)],
+    [(0, 0), (0, 1), (1, 0), (2, 0), (3, -1), (3, 0)],
+    [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (2, 0)],
+    [(0, 0), (0, 1), (1, -1), (1, 0), (2, -2), (2, -1)],
+    [(0, 0), (0, 1), (0, 2), (1, 1), (2, 1), (3, 1)],
+    [(0, 0), (0, 1), (0, 2), (1, 1), (2, 0), (2, 1)],
+    [(0, 0), (0, 1), (0, 2), (1, -1), (1, 0), (1, 2)],
+    [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2), (2, 0)],
+    [(0, 0), (0, 1), (0, 2), (1, -1), (1, 0), (2, -1)],
+    [(0, 0), (0, 1), (0, 2), (1, -2), (1, -1), (1, 0)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 1)],
+    [(0, 0), (1, -2), (1, -1), (1, 0), (1, 1), (2, -1)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (1, 0)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 2)],
+    [(0, 0), (0, 1), (1, -1), (1, 0), (2, 0), (2, 1)],
+    [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (2, 1)],
+    [(0, 0), (0, 1), (0, 2), (1, -1), (1, 0), (2, 0)],
+    [(0, 0), (0, 1), (1, -1), (1, 0), (1, 1), (2, -1)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (1, 1)],
+    [(0, 0), (0, 1), (1, -2), (1, -1), (1, 0), (2, -2)],
+    [(0, 0), (0, 1), (1, -2), (1, -1), (1, 0), (2, -1)],
+    [(0, 0), (1, -2), (1, -1), (1, 0), (1, 1), (2, 0)],
+    [(0, 0), (0, 1), (0, 2), (1, 0), (2, -1), (2, 0)],
+    [(0, 0), (0, 1), (1, -1), (1, 0), (2, 0), (3, 0)],
+    [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (1, 2)],
+    [(0, 0), (0, 1), (1, 0), (2, -1), (2, 0), (3, 0)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 1), (2, 1)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, -1), (1, 0)],
+    [(0, 0), (0, 1), (1, -1), (1, 0), (1, 1), (2, 0)],
+    [(0, 0), (0, 1), (1, -2), (1, -1), (1, 0), (2, 0)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (2, 0)],
+    [(0, 0), (0, 1), (0, 2), (0, 3), (1, 0), (1, 3)],
+];
+
+/// Checks that [`HEXOS`] are exactly the free hexominoes except the straight one.
+pub fn check_hexos() -> Result<(), String> {
+    let straight: Vec<Cell> = (0..6).map(|y| (0, y)).collect();
+    let expected: BTreeSet<_> = free_polyominoes(6)
+        .into_iter()
+        .filter(|poly| *poly != straight)
+        .collect();
+    let mut actual = BTreeSet::new();
+    for hexo in HEXOS {
+        if normalize(&hexo) != hexo {
+            return Err(format!("{:?} is not in canonical form", hexo));
+        }
+        if !actual.insert(hexo.to_vec()) {
+            return Err(format!("{:?} appears more than once", hexo));
+        }
+    }
+    if actual != expected {
+        return Err("the hexos are not the free hexominoes except the straight one".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    #[test]
+    fn free_polyomino_counts() {
+        let counts: Vec<_> = (1..=6).map(|n| free_polyominoes(n).len()).collect();
+        check!(counts == [1, 1, 2, 5, 12, 35]);
+    }
+
+    #[test]
+    fn hexos_are_free_hexominoes_without_straight_one() {
+        check!(check_hexos() == Ok(()));
+    }
+
+    #[test]
+    fn normalize_ignores_transforms() {
+        let hexo = HEXOS[2].to_vec();
+        let moved: Vec<_> = hexo
+            .iter()
+            .map(|&cell| {
+                let (x, y) = rotate(reflect(cell));
+                (x + 3, y - 5)
+            })
+            .collect();
+        check!(normalize(&moved) == hexo);
+    }
+
+    #[test]
+    fn borders_form_the_outline() {
+        for hexo in HEXOS {
+            let borders = borders(&hexo);
+            let cells: BTreeSet<_> = hexo.iter().copied().collect();
+            // Every cell has 4 edges, and every pair of adjacent cells shares one.
+            let adjacent = hexo
+                .iter()
+                .filter(|(x, y)| cells.contains(&(x + 1, *y)))
+                .count()
+                + hexo
+                    .iter()
+                    .filter(|(x, y)| cells.contains(&(*x, y + 1)))
+                    .count();
+            check!(borders.len() == 4 * 6 - 2 * adjacent);
+        }
+    }
+}
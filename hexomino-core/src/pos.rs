@@ -2,7 +2,9 @@ use std::ops::Add;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Eq, PartialEq, Default, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Eq, PartialEq, Hash, Default, PartialOrd, Ord, Debug, Serialize, Deserialize,
+)]
 pub struct Pos {
     pub x: i32,
     pub y: i32,
@@ -31,6 +33,12 @@ impl Pos {
     }
 }
 
+impl From<(i32, i32)> for Pos {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self { x, y }
+    }
+}
+
 macro_rules! impl_add_for_point {
     ($ltype:ty, $rtype: ty) => {
         impl Add<$rtype> for $ltype {
@@ -0,0 +1,231 @@
+use getset::{CopyGetters, Getters};
+use thiserror::Error;
+
+use super::{
+    board::Board,
+    board_layout::BoardLayout,
+    board_spec::BoardSpec,
+    dlx::ExactCover,
+    hexo::{Hexo, HexoSet, MovedHexo, PlacedHexo},
+    piece_set::PieceSet,
+    pos::Pos,
+    state::Player,
+    zobrist::SplitMix64,
+};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    #[error("the layout has {open} open tiles, but the hexos cover {hexos}")]
+    AreaMismatch { open: usize, hexos: usize },
+}
+
+/// A tiling puzzle: cover every open tile of the layout with the hexos, each used exactly once.
+#[derive(CopyGetters, Getters, Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    #[getset(get_copy = "pub")]
+    layout: BoardLayout,
+    #[getset(get = "pub")]
+    hexos: HexoSet,
+}
+
+impl Puzzle {
+    /// The number of times [`Puzzle::generate`] tries to build a puzzle before giving up.
+    const MAX_GENERATE_ATTEMPTS: usize = 64;
+
+    pub fn new(layout: BoardLayout, hexos: HexoSet) -> Result<Self, PuzzleError> {
+        let open = layout.spec().num_tiles() - layout.blocked().len();
+        let hexos_area = hexos.iter().map(|hexo| hexo.tiles().count()).sum();
+        if open != hexos_area {
+            return Err(PuzzleError::AreaMismatch {
+                open,
+                hexos: hexos_area,
+            });
+        }
+        Ok(Self { layout, hexos })
+    }
+
+    /// Generates a puzzle with a unique solution, by placing `num_hexos` hexos of the piece set
+    /// next to each other on an empty board of the spec and blocking the tiles they leave empty.
+    ///
+    /// The puzzle only depends on the arguments. Returns `None` if the piece set has fewer than
+    /// `num_hexos` hexos, or if no puzzle with a unique solution is found after a number of
+    /// attempts.
+    pub fn generate(
+        spec: BoardSpec,
+        piece_set: PieceSet,
+        num_hexos: usize,
+        seed: u64,
+    ) -> Option<Puzzle> {
+        if num_hexos > piece_set.len() {
+            return None;
+        }
+        let mut rng = SplitMix64(seed);
+        (0..Self::MAX_GENERATE_ATTEMPTS).find_map(|_| {
+            let hexos = HexoSet::all(piece_set).sample(num_hexos, rng.next_u64());
+            let mut board = Board::new(spec.into(), piece_set);
+            for hexo in hexos.iter() {
+                let touching: Vec<_> = board
+                    .placements(hexo)
+                    .filter(|placement| {
+                        board.occupied().is_empty()
+                            || placement.tiles().any(|tile| touches_placed(&board, tile))
+                    })
+                    .collect();
+                if touching.is_empty() {
+                    return None;
+                }
+                let placement = touching[(rng.next_u64() % touching.len() as u64) as usize];
+                board
+                    .place(PlacedHexo::new(placement, Player::First))
+                    .unwrap();
+            }
+            let empty = spec.all_tiles().filter(|&tile| !board.is_placed(tile));
+            let layout = BoardLayout::new(spec, empty).unwrap();
+            let puzzle = Puzzle::new(layout, hexos).unwrap();
+            puzzle.has_unique_solution().then_some(puzzle)
+        })
+    }
+
+    /// Returns up to `limit` solutions, each as the placements of the hexos in the order of
+    /// their ids.
+    pub fn solutions(&self, limit: usize) -> Vec<Vec<MovedHexo>> {
+        let spec = self.layout.spec();
+        let board = Board::new(self.layout, self.hexos.piece_set());
+        // The columns are the open tiles, followed by the hexos.
+        let mut column_of_tile = vec![None; spec.num_tiles()];
+        let mut num_open = 0;
+        for tile in spec.all_tiles() {
+            if !self.layout.is_blocked(tile) {
+                column_of_tile[spec.tile_index(tile)] = Some(num_open);
+                num_open += 1;
+            }
+        }
+        let hexos: Vec<Hexo> = self.hexos.iter().collect();
+        let mut problem = ExactCover::new(num_open + hexos.len());
+        let mut placements = vec![];
+        for (i, &hexo) in hexos.iter().enumerate() {
+            for placement in board.placements(hexo) {
+                let mut columns: Vec<_> = placement
+                    .tiles()
+                    .map(|tile| column_of_tile[spec.tile_index(tile)].unwrap())
+                    .collect();
+                columns.push(num_open + i);
+                problem.add_row(&columns);
+                placements.push(placement);
+            }
+        }
+        problem
+            .solve(limit)
+            .into_iter()
+            .map(|rows| rows.into_iter().map(|row| placements[row]).collect())
+            .collect()
+    }
+
+    /// Returns a solution of the puzzle, if there is any.
+    pub fn solve(&self) -> Option<Vec<MovedHexo>> {
+        self.solutions(1).pop()
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.solutions(2).len() == 1
+    }
+
+    /// Returns true if the placements use every hexo once and cover every open tile once.
+    pub fn is_solution(&self, placements: &[MovedHexo]) -> bool {
+        let mut board = Board::new(self.layout, self.hexos.piece_set());
+        let mut used = HexoSet::empty(self.hexos.piece_set());
+        for &placement in placements {
+            let hexo = placement.hexo();
+            if hexo.piece_set() != self.hexos.piece_set() || !self.hexos.has(hexo) {
+                return false;
+            }
+            if used.has(hexo) {
+                return false;
+            }
+            used.add(hexo);
+            if board
+                .place(PlacedHexo::new(placement, Player::First))
+                .is_err()
+            {
+                return false;
+            }
+        }
+        used == self.hexos
+    }
+}
+
+/// Returns true if the tile shares an edge with a placed hexo.
+fn touches_placed(board: &Board, tile: Pos) -> bool {
+    [(1, 0), (-1, 0), (0, 1), (0, -1)].into_iter().any(|delta| {
+        let neighbor = tile + Pos::from(delta);
+        board.in_bound(neighbor) && board.is_placed(neighbor)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{check, let_assert};
+
+    use super::*;
+
+    fn rectangle(rows: usize, cols: usize) -> BoardLayout {
+        BoardSpec::new(rows, cols).unwrap().into()
+    }
+
+    #[test]
+    fn counts_pentomino_rectangle_tilings() {
+        // The 3x20 rectangle has 2 tilings up to symmetry, and each has 4 images under the
+        // symmetries of the rectangle.
+        let puzzle = Puzzle::new(rectangle(3, 20), HexoSet::all(PieceSet::pentominoes())).unwrap();
+        let solutions = puzzle.solutions(100);
+        check!(solutions.len() == 8);
+        check!(solutions
+            .iter()
+            .all(|solution| puzzle.is_solution(solution)));
+        check!(!puzzle.has_unique_solution());
+    }
+
+    #[test]
+    fn rejects_area_mismatch() {
+        let hexos = HexoSet::all(PieceSet::pentominoes()).sample(3, 1);
+        check!(
+            Puzzle::new(rectangle(4, 4), hexos)
+                == Err(PuzzleError::AreaMismatch {
+                    open: 16,
+                    hexos: 15
+                })
+        );
+    }
+
+    #[test]
+    fn is_solution_checks_every_hexo_and_tile() {
+        let spec = BoardSpec::new(6, 6).unwrap();
+        let_assert!(Some(puzzle) = Puzzle::generate(spec, PieceSet::pentominoes(), 3, 2));
+        let_assert!(Some(solution) = puzzle.solve());
+        check!(puzzle.is_solution(&solution));
+        check!(!puzzle.is_solution(&solution[1..]));
+        let mut twice = solution.clone();
+        twice[1] = twice[0];
+        check!(!puzzle.is_solution(&twice));
+    }
+
+    #[test]
+    fn generated_puzzles_have_a_unique_solution() {
+        let spec = BoardSpec::new(8, 8).unwrap();
+        let generate = |seed| Puzzle::generate(spec, PieceSet::pentominoes(), 5, seed);
+        for seed in 0..8 {
+            let_assert!(Some(puzzle) = generate(seed));
+            check!(puzzle.hexos().len() == 5);
+            let_assert!([solution] = &puzzle.solutions(2)[..]);
+            check!(puzzle.is_solution(solution));
+            check!(generate(seed) == Some(puzzle));
+        }
+    }
+
+    #[test]
+    fn generate_needs_enough_hexos_in_the_piece_set() {
+        let spec = BoardSpec::new(8, 8).unwrap();
+        let pentominoes = PieceSet::pentominoes();
+        check!(Puzzle::generate(spec, pentominoes, pentominoes.len() + 1, 0) == None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Seeded generators of random positions, for tests, benchmarks and puzzles.
+//!
+//! Every generator only depends on its seed and the position it starts from, so a position can
+//! be reproduced from the seed alone.
+
+use super::{
+    state::{Action, GamePhase, State},
+    zobrist::SplitMix64,
+};
+
+/// Plays legal actions chosen uniformly at random.
+///
+/// In [`GamePhase::Pick`], every remaining hexo is equally likely to be picked, so a whole draft
+/// deals the hexos uniformly at random. In [`GamePhase::Place`], every distinct placement of
+/// [`State::legal_actions`] is equally likely.
+pub struct RandomPlayer {
+    rng: SplitMix64,
+}
+
+impl RandomPlayer {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64(seed),
+        }
+    }
+
+    /// Returns a legal action of the current player, or `None` if the game has ended.
+    pub fn choose(&mut self, state: &State) -> Option<Action> {
+        let count = state.num_legal_actions();
+        if count == 0 {
+            return None;
+        }
+        let index = (self.rng.next_u64() % count as u64) as usize;
+        state.legal_actions().nth(index)
+    }
+
+    /// Plays random actions until `num_actions` actions have been played in the game, or the
+    /// game ends.
+    pub fn play_until(&mut self, state: &mut State, num_actions: usize) {
+        while state.actions().len() < num_actions {
+            let action = match self.choose(state) {
+                Some(action) => action,
+                None => return,
+            };
+            state.current_player_play(action).unwrap();
+        }
+    }
+
+    /// Plays random actions until the pick phase is over.
+    pub fn draft(&mut self, state: &mut State) {
+        while state.phase() == GamePhase::Pick {
+            let action = self.choose(state).unwrap();
+            state.current_player_play(action).unwrap();
+        }
+    }
+
+    /// Plays random actions until the game ends.
+    pub fn play_out(&mut self, state: &mut State) {
+        self.play_until(state, usize::MAX);
+    }
+}
+
+impl State {
+    /// Creates a standard game whose hexos are drafted at random with the seed.
+    pub fn new_random_place(seed: u64) -> Self {
+        let mut state = State::new();
+        RandomPlayer::new(seed).draft(&mut state);
+        state
+    }
+
+    /// Creates a standard game played at random with the seed until it ends.
+    pub fn new_random_end(seed: u64) -> Self {
+        let mut state = State::new();
+        RandomPlayer::new(seed).play_out(&mut state);
+        state
+    }
+
+    /// Creates a standard game played at random with the seed until `num_actions` actions have
+    /// been played, or it ends. The picks count as actions.
+    pub fn new_random(seed: u64, num_actions: usize) -> Self {
+        let mut state = State::new();
+        RandomPlayer::new(seed).play_until(&mut state, num_actions);
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::check;
+
+    use super::*;
+    use crate::{BoardSpec, HexoSet, PieceSet, Player};
+
+    fn pentomino_game() -> State {
+        State::with_pool(
+            BoardSpec::new(5, 6).unwrap(),
+            HexoSet::all(PieceSet::pentominoes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn same_seed_plays_the_same_game() {
+        let play = |seed| {
+            let mut state = pentomino_game();
+            RandomPlayer::new(seed).play_out(&mut state);
+            state.actions().collect::<Vec<_>>()
+        };
+        check!(play(1) == play(1));
+        check!((2..10).any(|seed| play(seed) != play(1)));
+    }
+
+    #[test]
+    fn random_draft_ends_in_place_phase() {
+        let mut state = pentomino_game();
+        RandomPlayer::new(3).draft(&mut state);
+        check!(state.phase() == GamePhase::Place);
+        check!(state.actions().len() == 12);
+        check!(state.inventory().remaining_hexos().is_empty());
+    }
+
+    #[test]
+    fn random_drafts_deal_every_hexo_to_both_players() {
+        let mut state = pentomino_game();
+        let drafts: Vec<_> = (0..20)
+            .map(|seed| {
+                let mut state = state.clone();
+                RandomPlayer::new(seed).draft(&mut state);
+                state
+            })
+            .collect();
+        for hexo in state.piece_set().hexos() {
+            for player in [Player::First, Player::Second] {
+                check!(
+                    drafts
+                        .iter()
+                        .any(|draft| draft.inventory().owner_of(hexo) == Some(player)),
+                    "{hexo:?} is never dealt to {player:?}"
+                );
+            }
+        }
+        RandomPlayer::new(0).play_out(&mut state);
+        check!(state.phase() == GamePhase::End);
+    }
+
+    #[test]
+    fn random_game_stops_at_move_number() {
+        let mut state = pentomino_game();
+        RandomPlayer::new(5).play_until(&mut state, 14);
+        check!(state.actions().len() == 14);
+        check!(state.phase() == GamePhase::Place);
+        let replayed = {
+            let mut replayed = pentomino_game();
+            RandomPlayer::new(5).play_until(&mut replayed, 14);
+            replayed
+        };
+        check!(replayed == state);
+    }
+
+    #[test]
+    fn random_standard_game_is_legal() {
+        let state = State::new_random_end(7);
+        check!(state.phase() == GamePhase::End);
+        check!(let Ok(_) = State::from_actions(&state.actions().collect::<Vec<_>>()));
+        check!(
+            State::new_random(7, 40)
+                == State::from_actions(&state.actions().take(40).collect::<Vec<_>>()).unwrap()
+        );
+    }
+}
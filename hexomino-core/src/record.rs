@@ -0,0 +1,831 @@
+//! A text format for game records, in the spirit of PGN.
+//!
+//! A record starts with tag pairs, one per line, followed by the actions in the notation of
+//! [`crate::notation`], separated by whitespace:
+//!
+//! ```text
+//! [First "alice"]
+//! [Second "bob"]
+//! [Config "Normal"]
+//! [Game "0"]
+//! [TimeControl "60"]
+//! [Result "Second"]
+//! [EndReason "NoValidMove"]
+//!
+//! P3 P17 P5 ...
+//! P17:F2@c5 ...
+//! ```
+//!
+//! Only the `First` and `Second` tags are required. A game other than the standard one has tags
+//! for how it differs, which are left out when they have the standard value:
+//!
+//! - `Third` and `Fourth` are the names of the players after the first two.
+//! - `Board` is the number of rows and columns, e.g. `6x10`, and `Blocked` the blocked tiles as
+//!   cells of the notation, e.g. `c1 d5`.
+//! - `PieceSet` is `Pentominoes`, or the tiles of the pieces as `x,y` separated by spaces, with
+//!   the pieces separated by `/`, e.g. `0,0 1,0/0,0 0,1 1,1`. `Pool` is the ids of the hexos the
+//!   game is played with, if it is not all the pieces of the set.
+//! - `Draft` is `Snake`, `RandomDeal` followed by the seed, or `Blind`. `Bans` is the number of
+//!   bans per player, and `Win` is `Misere` or `AreaScoring`.
+//! - `Result` is `Draw` for a game without a winner, and `Eliminated` is the players who could
+//!   not place a hexo, in the order they could not.
+//!
+//! Tags other than the ones above are kept as they are. In tag values, `\`, `"` and line breaks
+//! are escaped with a backslash. A file may contain several records one after another, e.g. all
+//! the games of a match. Lines starting with `;` are comments.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use super::{
+    board_layout::BoardLayout,
+    board_spec::BoardSpec,
+    hexo::{HexoSet, Tiles},
+    notation::{Cell, NotationError},
+    piece_set::PieceSet,
+    pos::Pos,
+    state::{self, Action, DraftRule, GamePhase, Rules, Seat, State, WinRule},
+};
+use thiserror::Error;
+
+/// Why a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    NoValidMove,
+    TimeLimitExceed,
+}
+
+impl EndReason {
+    fn name(self) -> &'static str {
+        match self {
+            EndReason::NoValidMove => "NoValidMove",
+            EndReason::TimeLimitExceed => "TimeLimitExceed",
+        }
+    }
+}
+
+/// The result of a finished game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    /// The seat of the winner, or `None` if the game is a draw.
+    pub winner: Option<Seat>,
+    pub reason: Option<EndReason>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameRecord {
+    /// The names of the players, in the order of their seats.
+    pub players: Vec<String>,
+    /// The name of the match config.
+    pub config: Option<String>,
+    /// The 0-based index of the game in the match.
+    pub game_index: Option<u32>,
+    /// The time limit of each turn.
+    pub time_control: Option<Duration>,
+    /// The board, with its blocked tiles.
+    pub layout: BoardLayout,
+    pub piece_set: PieceSet,
+    /// The hexos the game is played with, or `None` for all the pieces of the set.
+    pub pool: Option<HexoSet>,
+    /// The rules of the game, whose number of players is that of [`GameRecord::players`].
+    pub rules: Rules,
+    /// The result of the game, or `None` if the game is unfinished.
+    pub result: Option<GameResult>,
+    /// The seats of the players who could not place a hexo, in the order they could not.
+    pub eliminated: Vec<Seat>,
+    /// Other tags, in the order they appear. A tag whose name is not made of ASCII letters,
+    /// digits and `_`, or is one of the tags above, is not written.
+    pub extra_tags: Vec<(String, String)>,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    #[error("malformed tag pair")]
+    MalformedTag,
+    #[error("tag {0} appears more than once")]
+    DuplicateTag(String),
+    #[error("missing tag {0}")]
+    MissingTag(&'static str),
+    #[error("invalid value {value:?} of tag {tag}")]
+    InvalidTagValue { tag: &'static str, value: String },
+    #[error("invalid action {action:?}: {error}")]
+    InvalidAction {
+        action: String,
+        error: NotationError,
+    },
+    #[error("no record found")]
+    Empty,
+    #[error("more than one record found")]
+    MultipleRecords,
+}
+
+/// An error when reading a record, with the 1-based line number it occurs at.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("line {line}: {kind}")]
+pub struct RecordError {
+    pub line: usize,
+    pub kind: RecordErrorKind,
+}
+
+impl GameRecord {
+    pub fn new(players: Vec<String>, actions: Vec<Action>) -> Self {
+        Self {
+            players,
+            actions,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the record of the game of the state, with the names of the players in the order
+    /// of their seats.
+    pub fn from_state(players: Vec<String>, state: &State) -> Self {
+        let piece_set = state.piece_set();
+        let pool = state.pool();
+        let result = (state.phase() == GamePhase::End).then(|| GameResult {
+            winner: state.winning_seat(),
+            reason: Some(EndReason::NoValidMove),
+        });
+        Self {
+            layout: state.board().layout(),
+            piece_set,
+            pool: (pool != HexoSet::all(piece_set)).then_some(pool),
+            rules: state.rules(),
+            result,
+            eliminated: state.eliminated().to_vec(),
+            ..Self::new(players, state.actions().collect())
+        }
+    }
+
+    /// Returns the state at the start of the game, before any of the actions is played.
+    pub fn initial_state(&self) -> Result<State, state::Error> {
+        let rules = Rules {
+            num_players: self.players.len(),
+            ..self.rules
+        };
+        let pool = self
+            .pool
+            .clone()
+            .unwrap_or_else(|| HexoSet::all(self.piece_set));
+        State::with_rules(self.layout, pool, rules)
+    }
+
+    /// Reads all the records in the text.
+    pub fn parse_all(text: &str) -> Result<Vec<GameRecord>, RecordError> {
+        let mut records = vec![];
+        let mut builder = RecordBuilder::default();
+        for (idx, line) in text.lines().enumerate() {
+            let line_number = idx + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                // The tag pairs end at an empty line, even if no action follows.
+                builder.in_movetext |= builder.line.is_some();
+                continue;
+            }
+            if line.starts_with(';') {
+                continue;
+            }
+            let error = |kind| RecordError {
+                line: line_number,
+                kind,
+            };
+            if line.starts_with('[') {
+                if builder.in_movetext {
+                    records.push(std::mem::take(&mut builder).build()?);
+                }
+                let (tag, value) =
+                    parse_tag(line).ok_or_else(|| error(RecordErrorKind::MalformedTag))?;
+                builder.line = builder.line.or(Some(line_number));
+                builder.add_tag(tag, value).map_err(error)?;
+            } else {
+                builder.line = builder.line.or(Some(line_number));
+                builder.in_movetext = true;
+                let piece_set = builder.piece_set.unwrap_or_default();
+                for token in line.split_whitespace() {
+                    let action = Action::parse_in(token, piece_set).map_err(|notation_error| {
+                        error(RecordErrorKind::InvalidAction {
+                            action: token.to_string(),
+                            error: notation_error,
+                        })
+                    })?;
+                    builder.record.actions.push(action);
+                }
+            }
+        }
+        if builder.line.is_some() {
+            records.push(builder.build()?);
+        }
+        Ok(records)
+    }
+
+    fn tags(&self) -> Vec<(&str, String)> {
+        let mut tags: Vec<_> = SEAT_NAMES
+            .into_iter()
+            .zip(self.players.iter().cloned())
+            .collect();
+        if let Some(config) = &self.config {
+            tags.push(("Config", config.clone()));
+        }
+        if let Some(game_index) = self.game_index {
+            tags.push(("Game", game_index.to_string()));
+        }
+        if let Some(time_control) = self.time_control {
+            tags.push(("TimeControl", format_duration(time_control)));
+        }
+        let spec = self.layout.spec();
+        if spec != BoardSpec::STANDARD {
+            tags.push(("Board", format!("{}x{}", spec.rows(), spec.cols())));
+        }
+        let blocked: Vec<_> = self
+            .layout
+            .blocked_tiles()
+            .map(|tile| Cell(tile).to_string())
+            .collect();
+        if !blocked.is_empty() {
+            tags.push(("Blocked", blocked.join(" ")));
+        }
+        if self.piece_set != PieceSet::hexominoes() {
+            tags.push(("PieceSet", format_piece_set(self.piece_set)));
+        }
+        if let Some(pool) = &self.pool {
+            let ids: Vec<_> = pool.iter().map(|hexo| hexo.id().to_string()).collect();
+            tags.push(("Pool", ids.join(" ")));
+        }
+        match self.rules.draft {
+            DraftRule::Alternate => {}
+            DraftRule::Snake => tags.push(("Draft", "Snake".to_string())),
+            DraftRule::RandomDeal { seed } => tags.push(("Draft", format!("RandomDeal {seed}"))),
+            DraftRule::Blind => tags.push(("Draft", "Blind".to_string())),
+        }
+        if self.rules.bans_per_player != 0 {
+            tags.push(("Bans", self.rules.bans_per_player.to_string()));
+        }
+        if self.rules.win != WinRule::Normal {
+            tags.push(("Win", format!("{:?}", self.rules.win)));
+        }
+        let result = match self.result {
+            Some(GameResult {
+                winner: Some(seat), ..
+            }) => SEAT_NAMES[seat.index()],
+            Some(GameResult { winner: None, .. }) => "Draw",
+            None => "*",
+        };
+        tags.push(("Result", result.to_string()));
+        if let Some(reason) = self.result.and_then(|result| result.reason) {
+            tags.push(("EndReason", reason.name().to_string()));
+        }
+        if !self.eliminated.is_empty() {
+            let seats: Vec<_> = self
+                .eliminated
+                .iter()
+                .map(|seat| SEAT_NAMES[seat.index()])
+                .collect();
+            tags.push(("Eliminated", seats.join(" ")));
+        }
+        let extra_tags = self
+            .extra_tags
+            .iter()
+            .filter(|(tag, _)| is_tag_name(tag) && !STANDARD_TAGS.contains(&tag.as_str()))
+            .map(|(tag, value)| (tag.as_str(), value.clone()));
+        tags.extend(extra_tags);
+        tags
+    }
+}
+
+/// The names of the players in the tags, which are also how the seats are written in them.
+const SEAT_NAMES: [&str; Seat::MAX] = ["First", "Second", "Third", "Fourth"];
+
+/// The tags with a field of their own in [`GameRecord`].
+const STANDARD_TAGS: [&str; 17] = [
+    "First",
+    "Second",
+    "Third",
+    "Fourth",
+    "Config",
+    "Game",
+    "TimeControl",
+    "Board",
+    "Blocked",
+    "PieceSet",
+    "Pool",
+    "Draft",
+    "Bans",
+    "Win",
+    "Result",
+    "EndReason",
+    "Eliminated",
+];
+
+/// The number of actions written on each line.
+const ACTIONS_PER_LINE: usize = 8;
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (tag, value) in self.tags() {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r");
+            writeln!(f, "[{} \"{}\"]", tag, value)?;
+        }
+        writeln!(f)?;
+        for actions in self.actions.chunks(ACTIONS_PER_LINE) {
+            let line = actions
+                .iter()
+                .map(|action| action.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = RecordError;
+
+    /// Reads a single record. Use [`GameRecord::parse_all`] for a text with several records.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut records = Self::parse_all(s)?;
+        match records.len() {
+            0 => Err(RecordError {
+                line: 1,
+                kind: RecordErrorKind::Empty,
+            }),
+            1 => Ok(records.pop().unwrap()),
+            _ => Err(RecordError {
+                line: 1,
+                kind: RecordErrorKind::MultipleRecords,
+            }),
+        }
+    }
+}
+
+/// Writes several records, separated by empty lines.
+pub fn write_records(records: &[GameRecord]) -> String {
+    records
+        .iter()
+        .map(|record| record.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses a line of the form `[Tag "value"]`.
+fn parse_tag(line: &str) -> Option<(&str, String)> {
+    let line = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (tag, rest) = line.split_once(' ')?;
+    if !is_tag_name(tag) {
+        return None;
+    }
+    let quoted = rest.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                c => c,
+            }),
+            '"' => return None,
+            c => value.push(c),
+        }
+    }
+    Some((tag, value))
+}
+
+fn parse_seat(s: &str) -> Option<Seat> {
+    SEAT_NAMES
+        .iter()
+        .position(|name| *name == s)
+        .and_then(Seat::new)
+}
+
+fn format_piece_set(piece_set: PieceSet) -> String {
+    if piece_set == PieceSet::pentominoes() {
+        return "Pentominoes".to_string();
+    }
+    let pieces: Vec<_> = piece_set
+        .hexos()
+        .map(|hexo| {
+            let tiles: Vec<_> = hexo
+                .tiles()
+                .map(|tile| format!("{},{}", tile.x, tile.y))
+                .collect();
+            tiles.join(" ")
+        })
+        .collect();
+    pieces.join("/")
+}
+
+fn parse_piece_set(s: &str) -> Option<PieceSet> {
+    match s {
+        "Hexominoes" => return Some(PieceSet::hexominoes()),
+        "Pentominoes" => return Some(PieceSet::pentominoes()),
+        _ => {}
+    }
+    let pieces = s
+        .split('/')
+        .map(|piece| {
+            piece
+                .split_whitespace()
+                .map(|tile| {
+                    let (x, y) = tile.split_once(',')?;
+                    Some(Pos::new(x.parse().ok()?, y.parse().ok()?))
+                })
+                .collect::<Option<Tiles>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+    PieceSet::new(pieces).ok()
+}
+
+fn parse_draft_rule(s: &str) -> Option<DraftRule> {
+    match s.split_once(' ') {
+        Some(("RandomDeal", seed)) => Some(DraftRule::RandomDeal {
+            seed: seed.parse().ok()?,
+        }),
+        Some(_) => None,
+        None => match s {
+            "Alternate" => Some(DraftRule::Alternate),
+            "Snake" => Some(DraftRule::Snake),
+            "Blind" => Some(DraftRule::Blind),
+            _ => None,
+        },
+    }
+}
+
+fn parse_win_rule(s: &str) -> Option<WinRule> {
+    [WinRule::Normal, WinRule::Misere, WinRule::AreaScoring]
+        .into_iter()
+        .find(|win| format!("{:?}", win) == s)
+}
+
+fn is_tag_name(tag: &str) -> bool {
+    !tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.subsec_millis() {
+        0 => duration.as_secs().to_string(),
+        millis => format!("{}.{:03}", duration.as_secs(), millis),
+    }
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (secs, millis) = match s.split_once('.') {
+        Some((secs, millis)) if millis.len() == 3 => (secs, millis.parse().ok()?),
+        Some(_) => return None,
+        None => (s, 0),
+    };
+    Some(Duration::from_secs(secs.parse().ok()?) + Duration::from_millis(millis))
+}
+
+#[derive(Default)]
+struct RecordBuilder {
+    /// The line the record starts at.
+    line: Option<usize>,
+    in_movetext: bool,
+    players: [Option<String>; Seat::MAX],
+    board: Option<BoardSpec>,
+    /// The value of the `Blocked` tag, which is read with the board.
+    blocked: Option<String>,
+    piece_set: Option<PieceSet>,
+    /// The value of the `Pool` tag, which is read with the piece set.
+    pool: Option<String>,
+    draft: Option<DraftRule>,
+    bans: Option<usize>,
+    win: Option<WinRule>,
+    result: Option<Option<GameResult>>,
+    reason: Option<EndReason>,
+    eliminated: Option<Vec<Seat>>,
+    record: GameRecord,
+}
+
+impl RecordBuilder {
+    fn add_tag(&mut self, tag: &str, value: String) -> Result<(), RecordErrorKind> {
+        fn set<T>(
+            slot: &mut Option<T>,
+            tag: &'static str,
+            value: String,
+            parse: impl FnOnce(&str) -> Option<T>,
+        ) -> Result<(), RecordErrorKind> {
+            if slot.is_some() {
+                return Err(RecordErrorKind::DuplicateTag(tag.to_string()));
+            }
+            *slot = Some(parse(&value).ok_or(RecordErrorKind::InvalidTagValue { tag, value })?);
+            Ok(())
+        }
+        if let Some(index) = SEAT_NAMES.iter().position(|name| *name == tag) {
+            return set(&mut self.players[index], SEAT_NAMES[index], value, |s| {
+                Some(s.to_string())
+            });
+        }
+        let record = &mut self.record;
+        match tag {
+            "Config" => set(&mut record.config, "Config", value, |s| Some(s.to_string())),
+            "Game" => set(&mut record.game_index, "Game", value, |s| s.parse().ok()),
+            "TimeControl" => set(
+                &mut record.time_control,
+                "TimeControl",
+                value,
+                parse_duration,
+            ),
+            "Board" => set(&mut self.board, "Board", value, |s| {
+                let (rows, cols) = s.split_once('x')?;
+                BoardSpec::new(rows.parse().ok()?, cols.parse().ok()?)
+            }),
+            "Blocked" => set(&mut self.blocked, "Blocked", value, |s| Some(s.to_string())),
+            "PieceSet" => set(&mut self.piece_set, "PieceSet", value, parse_piece_set),
+            "Pool" => set(&mut self.pool, "Pool", value, |s| Some(s.to_string())),
+            "Draft" => set(&mut self.draft, "Draft", value, parse_draft_rule),
+            "Bans" => set(&mut self.bans, "Bans", value, |s| s.parse().ok()),
+            "Win" => set(&mut self.win, "Win", value, parse_win_rule),
+            "Result" => set(&mut self.result, "Result", value, |s| match s {
+                "*" => Some(None),
+                "Draw" => Some(Some(GameResult {
+                    winner: None,
+                    reason: None,
+                })),
+                _ => Some(Some(GameResult {
+                    winner: Some(parse_seat(s)?),
+                    reason: None,
+                })),
+            }),
+            "EndReason" => set(&mut self.reason, "EndReason", value, |s| {
+                [EndReason::NoValidMove, EndReason::TimeLimitExceed]
+                    .into_iter()
+                    .find(|reason| reason.name() == s)
+            }),
+            "Eliminated" => set(&mut self.eliminated, "Eliminated", value, |s| {
+                s.split_whitespace().map(parse_seat).collect()
+            }),
+            _ => {
+                if record.extra_tags.iter().any(|(t, _)| t == tag) {
+                    return Err(RecordErrorKind::DuplicateTag(tag.to_string()));
+                }
+                record.extra_tags.push((tag.to_string(), value));
+                Ok(())
+            }
+        }
+    }
+
+    fn build(self) -> Result<GameRecord, RecordError> {
+        let line = self.line.unwrap_or(1);
+        let error = |kind| RecordError { line, kind };
+        let num_players = self
+            .players
+            .iter()
+            .take_while(|name| name.is_some())
+            .count();
+        if num_players < 2 || self.players[num_players..].iter().any(Option::is_some) {
+            return Err(error(RecordErrorKind::MissingTag(SEAT_NAMES[num_players])));
+        }
+        let players: Vec<_> = self.players.into_iter().flatten().collect();
+        let invalid = |tag, value: &str| {
+            error(RecordErrorKind::InvalidTagValue {
+                tag,
+                value: value.to_string(),
+            })
+        };
+        let in_game = |seat: &Seat| seat.index() < num_players;
+
+        let spec = self.board.unwrap_or_default();
+        let layout = match &self.blocked {
+            Some(blocked) => blocked
+                .split_whitespace()
+                .map(Cell::parse)
+                .collect::<Option<Vec<_>>>()
+                .and_then(|tiles| BoardLayout::new(spec, tiles).ok())
+                .ok_or_else(|| invalid("Blocked", blocked))?,
+            None => BoardLayout::from(spec),
+        };
+        let piece_set = self.piece_set.unwrap_or_default();
+        let pool = match &self.pool {
+            Some(ids) => Some(
+                ids.split_whitespace()
+                    .map(|id| piece_set.hexo(id.parse().ok()?))
+                    .collect::<Option<Vec<_>>>()
+                    .map(|hexos| {
+                        let mut pool = HexoSet::empty(piece_set);
+                        hexos.into_iter().for_each(|hexo| pool.add(hexo));
+                        pool
+                    })
+                    .ok_or_else(|| invalid("Pool", ids))?,
+            ),
+            None => None,
+        };
+        let rules = Rules {
+            num_players,
+            draft: self.draft.unwrap_or_default(),
+            bans_per_player: self.bans.unwrap_or(0),
+            win: self.win.unwrap_or_default(),
+        };
+        let eliminated = self.eliminated.unwrap_or_default();
+        if let Some(&seat) = eliminated.iter().find(|seat| !in_game(seat)) {
+            return Err(invalid("Eliminated", SEAT_NAMES[seat.index()]));
+        }
+
+        let winner = self.result.flatten().and_then(|result| result.winner);
+        if let Some(seat) = winner.filter(|seat| !in_game(seat)) {
+            return Err(invalid("Result", SEAT_NAMES[seat.index()]));
+        }
+        let result = match (self.result.flatten(), self.reason) {
+            (Some(result), reason) => Some(GameResult { reason, ..result }),
+            (None, None) => None,
+            (None, Some(reason)) => {
+                return Err(error(RecordErrorKind::InvalidTagValue {
+                    tag: "EndReason",
+                    value: reason.name().to_string(),
+                }))
+            }
+        };
+        Ok(GameRecord {
+            players,
+            layout,
+            piece_set,
+            pool,
+            rules,
+            result,
+            eliminated,
+            ..self.record
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hexo, Player, Transform};
+    use assert2::{check, let_assert};
+
+    fn sample_record() -> GameRecord {
+        let place = Hexo::new(0)
+            .apply(Transform::new(true, 1))
+            .place_at(Pos::new(1, 2));
+        GameRecord {
+            config: Some("Normal".to_string()),
+            game_index: Some(2),
+            time_control: Some(Duration::from_millis(30500)),
+            result: Some(GameResult {
+                winner: Some(Player::Second.into()),
+                reason: Some(EndReason::NoValidMove),
+            }),
+            extra_tags: vec![("Event".to_string(), "Spring \"Cup\"".to_string())],
+            ..GameRecord::new(
+                vec!["alice".to_string(), "bob \\ b".to_string()],
+                vec![
+                    Action::Pick(Hexo::new(1)),
+                    Action::Pick(Hexo::new(0)),
+                    Action::Place(place),
+                ],
+            )
+        }
+    }
+
+    #[test]
+    fn record_round_trips() {
+        let record = sample_record();
+        let text = record.to_string();
+        check!(text.parse() == Ok(record.clone()));
+
+        let unfinished = GameRecord::new(vec!["a".to_string(), "b".to_string()], vec![]);
+        let text = write_records(&[unfinished.clone(), record.clone()]);
+        check!(GameRecord::parse_all(&text) == Ok(vec![unfinished, record]));
+    }
+
+    fn names(num_players: usize) -> Vec<String> {
+        ["alice", "bob", "carol", "dave"][..num_players]
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn variant_games_replay_from_their_records() {
+        let layout: BoardLayout = "....../.#..#./....../......".parse().unwrap();
+        let custom = PieceSet::new(vec![
+            vec![Pos::new(0, 0), Pos::new(1, 0)],
+            vec![Pos::new(0, 0), Pos::new(1, 0), Pos::new(1, 1)],
+            vec![Pos::new(0, 0), Pos::new(0, 1), Pos::new(0, 2)],
+        ])
+        .unwrap();
+        let pentominoes = PieceSet::pentominoes();
+        let rules = |num_players, draft, win, bans_per_player| Rules {
+            num_players,
+            draft,
+            win,
+            bans_per_player,
+        };
+        let all = HexoSet::all(pentominoes);
+        let games = [
+            (rules(4, DraftRule::Snake, WinRule::Normal, 0), all.clone()),
+            (
+                rules(
+                    3,
+                    DraftRule::RandomDeal { seed: 5 },
+                    WinRule::AreaScoring,
+                    1,
+                ),
+                all.clone(),
+            ),
+            (
+                rules(2, DraftRule::Blind, WinRule::Misere, 0),
+                all.sample(6, 1),
+            ),
+            (
+                rules(2, DraftRule::Alternate, WinRule::AreaScoring, 0),
+                HexoSet::all(custom),
+            ),
+        ];
+        for (seed, (rules, pool)) in games.into_iter().enumerate() {
+            let num_players = rules.num_players;
+            let_assert!(Ok(mut state) = State::with_rules(layout, pool, rules));
+            crate::RandomPlayer::new(seed as u64).play_out(&mut state);
+            let record = GameRecord::from_state(names(num_players), &state);
+            let text = record.to_string();
+            let_assert!(Ok(parsed) = text.parse::<GameRecord>());
+            check!(parsed == record);
+            let_assert!(Ok(initial) = parsed.initial_state());
+            let_assert!(Ok(replayed) = State::replay(initial, &parsed.actions));
+            check!(replayed == state);
+            check!(parsed.eliminated == state.eliminated());
+        }
+    }
+
+    #[test]
+    fn draws_and_eliminations_are_written_by_seat() {
+        let record = GameRecord {
+            result: Some(GameResult {
+                winner: None,
+                reason: Some(EndReason::NoValidMove),
+            }),
+            eliminated: vec![Seat::new(2).unwrap(), Seat::new(0).unwrap()],
+            rules: Rules {
+                num_players: 3,
+                ..Rules::default()
+            },
+            ..GameRecord::new(names(3), vec![])
+        };
+        let text = record.to_string();
+        check!(text.contains("[Third \"carol\"]\n"));
+        check!(text.contains("[Result \"Draw\"]\n"));
+        check!(text.contains("[Eliminated \"Third First\"]\n"));
+        check!(text.parse() == Ok(record));
+    }
+
+    #[test]
+    fn written_records_can_be_read_back() {
+        let mut record = sample_record();
+        record.config = Some("two\nlines\r".to_string());
+        let text = record.to_string();
+        check!(text.lines().next() == Some("[First \"alice\"]"));
+        check!(text.parse() == Ok(record.clone()));
+
+        // Tags that cannot be read back as extra tags are left out.
+        let mut written = record.clone();
+        for tag in ["", "Round 1", "Round]", "First", "TimeControl"] {
+            written.extra_tags.push((tag.to_string(), "x".to_string()));
+        }
+        check!(written.to_string() == text);
+    }
+
+    #[test]
+    fn record_errors_have_line_numbers() {
+        let_assert!(Err(error) = "[First \"a\"]\n\nP0".parse::<GameRecord>());
+        check!(error.kind == RecordErrorKind::MissingTag("Second"));
+        let_assert!(Err(error) = "[First \"a\"]\n[Second \"b\"]\nP0 P9".parse::<GameRecord>());
+        check!(error.line == 3);
+        let_assert!(RecordErrorKind::InvalidAction { action, .. } = error.kind);
+        check!(action == "P9");
+        let_assert!(Err(error) = "[First \"a\"]\n[First \"b\"]".parse::<GameRecord>());
+        check!(
+            error
+                == RecordError {
+                    line: 2,
+                    kind: RecordErrorKind::DuplicateTag("First".to_string())
+                }
+        );
+        let_assert!(
+            Err(error) = "[First \"a\"]\n[Second \"b\"]\n[Fourth \"d\"]".parse::<GameRecord>()
+        );
+        check!(error.kind == RecordErrorKind::MissingTag("Third"));
+        let_assert!(
+            Err(error) = "[First \"a\"]\n[Second \"b\"]\n[Result \"Third\"]".parse::<GameRecord>()
+        );
+        check!(
+            error.kind
+                == RecordErrorKind::InvalidTagValue {
+                    tag: "Result",
+                    value: "Third".to_string()
+                }
+        );
+        let_assert!(Err(error) = "[Game \"x\"]".parse::<GameRecord>());
+        check!(
+            error.kind
+                == RecordErrorKind::InvalidTagValue {
+                    tag: "Game",
+                    value: "x".to_string()
+                }
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use getset::CopyGetters;
+
+use super::{
+    bitboard::Bitboard, board::Board, board_spec::BoardSpec, hexo::HexoSet, pos::Pos, state::State,
+};
+
+/// A connected component of the empty tiles of a board, where two tiles are connected if they
+/// share an edge.
+#[derive(CopyGetters, Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    spec: BoardSpec,
+    /// The tiles of the region, indexed by [`BoardSpec::tile_index`].
+    #[getset(get_copy = "pub")]
+    mask: Bitboard,
+    /// The corner of the bounding box with the smallest coordinates.
+    #[getset(get_copy = "pub")]
+    min: Pos,
+    /// The corner of the bounding box with the largest coordinates.
+    #[getset(get_copy = "pub")]
+    max: Pos,
+}
+
+impl Region {
+    /// Returns the number of tiles in the region.
+    pub fn size(&self) -> usize {
+        self.mask.len()
+    }
+
+    /// Returns the width and the height of the bounding box.
+    pub fn bounding_size(&self) -> (usize, usize) {
+        (
+            (self.max.x - self.min.x + 1) as usize,
+            (self.max.y - self.min.y + 1) as usize,
+        )
+    }
+
+    pub fn contains(&self, tile: Pos) -> bool {
+        self.spec.in_bound(tile) && self.mask.has(self.spec.tile_index(tile))
+    }
+
+    /// Returns the tiles of the region, in the order of their indices.
+    pub fn tiles(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.mask.iter().map(|idx| self.spec.tile_at(idx))
+    }
+}
+
+impl Board {
+    /// Returns the connected regions of the empty tiles, in the order of their first tile in
+    /// [`Board::all_tiles`].
+    pub fn regions(&self) -> Vec<Region> {
+        let spec = self.spec();
+        let mut visited = self.occupied();
+        let mut regions = vec![];
+        for start in spec.all_tiles() {
+            if visited.has(spec.tile_index(start)) {
+                continue;
+            }
+            visited.set(spec.tile_index(start));
+            let mut region = Region {
+                spec,
+                mask: Bitboard::EMPTY,
+                min: start,
+                max: start,
+            };
+            let mut stack = vec![start];
+            while let Some(tile) = stack.pop() {
+                region.mask.set(spec.tile_index(tile));
+                region.min = Pos::new(region.min.x.min(tile.x), region.min.y.min(tile.y));
+                region.max = Pos::new(region.max.x.max(tile.x), region.max.y.max(tile.y));
+                for delta in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                    let neighbor = tile + Pos::from(delta);
+                    if spec.in_bound(neighbor) && !visited.has(spec.tile_index(neighbor)) {
+                        visited.set(spec.tile_index(neighbor));
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            regions.push(region);
+        }
+        regions
+    }
+
+    /// Returns the hexos in `hexos` that can be placed inside the region.
+    pub fn fitting_hexos(&self, region: &Region, hexos: &HexoSet) -> HexoSet {
+        let mut fitting = HexoSet::empty(hexos.piece_set());
+        for hexo in hexos.iter() {
+            if self.placements_in(hexo, region).next().is_some() {
+                fitting.add(hexo);
+            }
+        }
+        fitting
+    }
+}
+
+impl State {
+    /// Returns the hexos that are not placed yet, which are the remaining hexos and the hexos
+    /// owned by the players.
+    pub fn unplaced_hexos(&self) -> HexoSet {
+        let inventory = self.inventory();
+        let mut hexos = inventory.remaining_hexos().clone();
+        for seat in inventory.seats() {
+            for hexo in inventory.hexos_of(seat).iter() {
+                hexos.add(hexo);
+            }
+        }
+        hexos
+    }
+
+    /// Returns the empty regions of the board, each with the hexos not placed yet that can
+    /// still be placed inside it.
+    pub fn regions(&self) -> Vec<(Region, HexoSet)> {
+        let hexos = self.unplaced_hexos();
+        self.board()
+            .regions()
+            .into_iter()
+            .map(|region| {
+                let fitting = self.board().fitting_hexos(&region, &hexos);
+                (region, fitting)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert2::{assert, check, let_assert};
+
+    use super::*;
+    use crate::{Action, BoardLayout, PieceSet, Transform};
+
+    #[test]
+    fn empty_board_is_one_region() {
+        let state = State::with_board_spec(BoardSpec::new(4, 6).unwrap());
+        let_assert!([region] = &state.board().regions()[..]);
+        check!(region.size() == 24);
+        check!(region.min() == Pos::new(0, 0));
+        check!(region.max() == Pos::new(5, 3));
+        check!(region.bounding_size() == (6, 4));
+        let pentomino = PieceSet::pentominoes().hexo(0).unwrap();
+        check!(state
+            .board()
+            .placements_in(pentomino, region)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn blocked_column_splits_the_board() {
+        let layout: BoardLayout = "..#.../..#.../..#...".parse().unwrap();
+        let state = State::with_layout(layout);
+        let_assert!([left, right] = &state.board().regions()[..]);
+        check!(left.size() == 6);
+        check!(left.bounding_size() == (2, 3));
+        check!(right.size() == 9);
+        check!(right.min() == Pos::new(3, 0));
+        check!(right.contains(Pos::new(5, 2)));
+        check!(!right.contains(Pos::new(2, 2)));
+        check!(right.tiles().count() == 9);
+    }
+
+    #[test]
+    fn regions_report_the_hexos_that_fit() {
+        // The O fits in the 2x2 square on the left, and the I only fits in the 3x3 square on the
+        // right.
+        let tiles = |cells: &[(i32, i32)]| cells.iter().copied().map(Pos::from).collect();
+        let piece_set = PieceSet::new(vec![
+            tiles(&[(0, 0), (1, 0), (0, 1), (1, 1)]),
+            tiles(&[(0, 0), (1, 0), (2, 0)]),
+            tiles(&[(0, 0)]),
+        ])
+        .unwrap();
+        let layout: BoardLayout = "..#.../..#.../###...".parse().unwrap();
+        let mut state = State::with_pool(layout, HexoSet::all(piece_set)).unwrap();
+        for id in 0..3 {
+            let hexo = piece_set.hexo(id).unwrap();
+            assert!(let Ok(_) = state.current_player_play(Action::Pick(hexo)));
+        }
+        let_assert!([(left, left_hexos), (right, right_hexos)] = &state.regions()[..]);
+        check!(left.size() == 4);
+        check!(left_hexos.iter().map(|hexo| hexo.id()).collect::<Vec<_>>() == [0, 2]);
+        check!(right.size() == 9);
+        check!(right_hexos.len() == 3);
+
+        // The second player places the I across the middle of the right square, and only the
+        // single tile of the first player fits above or below it.
+        let i = piece_set.hexo(1).unwrap();
+        let action = Action::Place(i.apply(Transform::I).move_to(Pos::new(3, 1)));
+        assert!(let Ok(_) = state.current_player_play(action));
+        let_assert!([_, (top, top_hexos), (bottom, bottom_hexos)] = &state.regions()[..]);
+        check!(top.bounding_size() == (3, 1));
+        check!(bottom.min() == Pos::new(3, 2));
+        check!(top_hexos.iter().map(|hexo| hexo.id()).collect::<Vec<_>>() == [2]);
+        check!(bottom_hexos == top_hexos);
+    }
+}
@@ -0,0 +1,487 @@
+use std::{fmt, str::FromStr};
+
+use super::{
+    bitboard::Bitboard,
+    board::Board,
+    board_layout::BoardLayout,
+    board_spec::BoardSpec,
+    hexo::{Hexo, HexoSet, MovedHexo, Tiles, Transform},
+    piece_set::PieceSet,
+    pos::Pos,
+    state::{DraftRule, GamePhase, Inventory, Rules, Seat, WinRule},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    #[error("snapshot version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[error("snapshot is malformed: {0}")]
+    Malformed(&'static str),
+    #[error("{0:?} appears more than once")]
+    DuplicateHexo(Hexo),
+    #[error("hexo {0:?} cannot be placed")]
+    CannotPlaceHexo(MovedHexo),
+    #[error("the position is not valid during phase {0:?}")]
+    InconsistentPhase(GamePhase),
+    #[error("the history does not lead to the position")]
+    InvalidHistory,
+    #[error("the hexos are not all from the same piece set")]
+    PieceSetMismatch,
+    #[error("a game cannot have {0} players")]
+    InvalidPlayerCount(usize),
+}
+
+/// A position of the game, without the history of how it is reached.
+///
+/// A snapshot is validated when it is turned into a [`State`](crate::State) with
+/// [`State::from_snapshot`](crate::State::from_snapshot).
+///
+/// Besides serde, a snapshot has a compact binary form ([`Snapshot::to_bytes`]) and a text form,
+/// which is the binary form in URL-safe base64 ([`fmt::Display`] and [`FromStr`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub(crate) version: u32,
+    pub(crate) phase: GamePhase,
+    #[serde(rename = "current_player")]
+    pub(crate) current_seat: Seat,
+    pub(crate) rules: Rules,
+    pub(crate) inventory: Inventory,
+    pub(crate) pending_pick: Option<Hexo>,
+    pub(crate) winner: Option<Seat>,
+    pub(crate) eliminated: Vec<Seat>,
+    pub(crate) board: Board,
+}
+
+const MAGIC: &[u8] = b"HXS";
+
+/// The byte that marks a piece set written out piece by piece.
+const CUSTOM_PIECE_SET: u8 = 0xff;
+
+/// The byte that marks no pending pick, or no winner.
+const NONE: u8 = 0xff;
+
+impl Snapshot {
+    /// The version of the snapshot format.
+    pub const VERSION: u32 = 1;
+
+    /// Encodes the snapshot in the binary form.
+    ///
+    /// The layout is the magic `HXS`, the version, the number of rows and columns of
+    /// the board, the blocked tiles, the piece set, the phase, the current player, the number of
+    /// players, the remaining hexos, the hexos of each player and the banned hexos as 128-bit
+    /// little-endian bitsets, the rules, the pending pick, the winner, the number of eliminated
+    /// players followed by the players, the number of placed hexos, and then for each placed hexo
+    /// its id, transform, displacement as two 16-bit little-endian integers, and player. Every
+    /// other field takes one byte.
+    ///
+    /// The blocked tiles are a bitset of the tiles in the order of [`BoardSpec::tile_index`], in
+    /// as many bytes as needed, with the lowest bit first.
+    ///
+    /// A built-in piece set is written as its index. Any other set is written as `0xff`, the
+    /// number of pieces, and then for each piece the number of tiles followed by the tiles as
+    /// pairs of 16-bit little-endian integers. The bounds that [`PieceSet::new`] puts on the
+    /// pieces keep the tiles and the displacements within these integers.
+    ///
+    /// The rules are the draft rule as `0` for [`DraftRule::Alternate`], `1` for
+    /// [`DraftRule::Snake`], `2` followed by the seed as a 64-bit little-endian integer for
+    /// [`DraftRule::RandomDeal`], or `3` for [`DraftRule::Blind`], the number of bans per
+    /// player, and the win rule as `0` for [`WinRule::Normal`], `1` for [`WinRule::Misere`], or
+    /// `2` for [`WinRule::AreaScoring`]. The pending pick is the hexo id, and the winner is the
+    /// player, or `0xff` if there is none.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(Self::VERSION as u8);
+        let spec = self.board.spec();
+        bytes.push(spec.rows() as u8);
+        bytes.push(spec.cols() as u8);
+        let blocked = self.board.layout().blocked();
+        for chunk in (0..spec.num_tiles()).collect::<Vec<_>>().chunks(8) {
+            let byte = chunk.iter().enumerate().fold(0u8, |byte, (bit, idx)| {
+                byte | (blocked.has(*idx) as u8) << bit
+            });
+            bytes.push(byte);
+        }
+        let piece_set = self.board.piece_set();
+        if piece_set.is_built_in() {
+            bytes.push(piece_set.index() as u8);
+        } else {
+            bytes.push(CUSTOM_PIECE_SET);
+            bytes.push(piece_set.len() as u8);
+            for hexo in piece_set.hexos() {
+                bytes.push(hexo.tiles().count() as u8);
+                for tile in hexo.tiles() {
+                    bytes.extend((tile.x as i16).to_le_bytes());
+                    bytes.extend((tile.y as i16).to_le_bytes());
+                }
+            }
+        }
+        bytes.push(self.phase as u8);
+        bytes.push(self.current_seat.index() as u8);
+        bytes.push(self.inventory.num_players() as u8);
+        let player_hexos = self
+            .inventory
+            .seats()
+            .map(|seat| self.inventory.hexos_of(seat));
+        for hexos in [self.inventory.remaining_hexos()]
+            .into_iter()
+            .chain(player_hexos)
+            .chain([self.inventory.banned_hexos()])
+        {
+            bytes.extend(hexos.bitset().to_le_bytes());
+        }
+        match self.rules.draft {
+            DraftRule::Alternate => bytes.push(0),
+            DraftRule::Snake => bytes.push(1),
+            DraftRule::RandomDeal { seed } => {
+                bytes.push(2);
+                bytes.extend(seed.to_le_bytes());
+            }
+            DraftRule::Blind => bytes.push(3),
+        }
+        bytes.push(self.rules.bans_per_player as u8);
+        bytes.push(self.rules.win as u8);
+        bytes.push(self.pending_pick.map_or(NONE, |hexo| hexo.id() as u8));
+        bytes.push(self.winner.map_or(NONE, |seat| seat.index() as u8));
+        bytes.push(self.eliminated.len() as u8);
+        bytes.extend(self.eliminated.iter().map(|seat| seat.index() as u8));
+        let placed_hexos = self.board.placed_hexos();
+        bytes.push(placed_hexos.len() as u8);
+        for placed_hexo in placed_hexos {
+            let moved_hexo = placed_hexo.moved_hexo();
+            bytes.push(moved_hexo.hexo().id() as u8);
+            bytes.push(moved_hexo.rhexo().transform().index() as u8);
+            bytes.extend((moved_hexo.displacement().x as i16).to_le_bytes());
+            bytes.extend((moved_hexo.displacement().y as i16).to_le_bytes());
+            bytes.push(placed_hexo.seat().index() as u8);
+        }
+        bytes
+    }
+
+    /// Decodes a snapshot from the binary form.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(SnapshotError::Malformed("not a snapshot"));
+        }
+        let version = reader.u8()? as u32;
+        if version != Self::VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let spec = BoardSpec::new(reader.u8()? as usize, reader.u8()? as usize)
+            .ok_or(SnapshotError::Malformed("invalid board size"))?;
+        let layout = reader.blocked(spec)?;
+        let piece_set = reader.piece_set()?;
+        let phase = match reader.u8()? {
+            0 => GamePhase::Pick,
+            1 => GamePhase::Place,
+            2 => GamePhase::End,
+            _ => return Err(SnapshotError::Malformed("invalid phase")),
+        };
+        let current_seat = reader.seat()?;
+        let num_players = reader.u8()? as usize;
+        let remaining_hexos = reader.hexo_set(piece_set)?;
+        let player_hexos = (0..num_players)
+            .map(|_| reader.hexo_set(piece_set))
+            .collect::<Result<_, _>>()?;
+        let banned_hexos = reader.hexo_set(piece_set)?;
+        let rules = reader.rules(num_players)?;
+        let pending_pick = match reader.u8()? {
+            NONE => None,
+            id => Some(
+                piece_set
+                    .hexo(id as usize)
+                    .ok_or(SnapshotError::Malformed("invalid hexo"))?,
+            ),
+        };
+        let winner = match reader.u8()? {
+            NONE => None,
+            id => Some(Seat::new(id as usize).ok_or(SnapshotError::Malformed("invalid player"))?),
+        };
+        let num_eliminated = reader.u8()?;
+        let eliminated = (0..num_eliminated)
+            .map(|_| reader.seat())
+            .collect::<Result<_, _>>()?;
+        let num_placed_hexos = reader.u8()?;
+        let placed_hexos: Vec<_> = (0..num_placed_hexos)
+            .map(|_| {
+                let hexo = piece_set
+                    .hexo(reader.u8()? as usize)
+                    .ok_or(SnapshotError::Malformed("invalid hexo"))?;
+                let transform = Transform::from_index(reader.u8()? as usize)
+                    .ok_or(SnapshotError::Malformed("invalid transform"))?;
+                let x = reader.i16()? as i32;
+                let y = reader.i16()? as i32;
+                let seat = reader.seat()?;
+                Ok(hexo
+                    .apply(transform)
+                    .move_to(Pos::new(x, y))
+                    .placed_by(seat))
+            })
+            .collect::<Result<_, SnapshotError>>()?;
+        if !reader.bytes.is_empty() {
+            return Err(SnapshotError::Malformed("trailing bytes"));
+        }
+        Ok(Self {
+            version,
+            phase,
+            current_seat,
+            rules,
+            inventory: Inventory::from_sets(remaining_hexos, player_hexos, banned_hexos)?,
+            pending_pick,
+            winner,
+            eliminated,
+            board: Board::from_placed_hexos(layout, piece_set, placed_hexos)?,
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::Malformed("unexpected end of snapshot"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16, SnapshotError> {
+        Ok(i16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn seat(&mut self) -> Result<Seat, SnapshotError> {
+        Seat::new(self.u8()? as usize).ok_or(SnapshotError::Malformed("invalid player"))
+    }
+
+    fn rules(&mut self, num_players: usize) -> Result<Rules, SnapshotError> {
+        let draft = match self.u8()? {
+            0 => DraftRule::Alternate,
+            1 => DraftRule::Snake,
+            2 => DraftRule::RandomDeal {
+                seed: u64::from_le_bytes(self.take(8)?.try_into().unwrap()),
+            },
+            3 => DraftRule::Blind,
+            _ => return Err(SnapshotError::Malformed("invalid draft rule")),
+        };
+        let bans_per_player = self.u8()? as usize;
+        let win = match self.u8()? {
+            0 => WinRule::Normal,
+            1 => WinRule::Misere,
+            2 => WinRule::AreaScoring,
+            _ => return Err(SnapshotError::Malformed("invalid win rule")),
+        };
+        Ok(Rules {
+            num_players,
+            draft,
+            bans_per_player,
+            win,
+        })
+    }
+
+    fn blocked(&mut self, spec: BoardSpec) -> Result<BoardLayout, SnapshotError> {
+        let bytes = self.take((spec.num_tiles() + 7) / 8)?;
+        let blocked: Bitboard = (0..bytes.len() * 8)
+            .filter(|idx| bytes[idx / 8] & (1 << (idx % 8)) != 0)
+            .collect();
+        if blocked.iter().any(|idx| idx >= spec.num_tiles()) {
+            return Err(SnapshotError::Malformed("invalid blocked tiles"));
+        }
+        BoardLayout::new(spec, blocked.iter().map(|idx| spec.tile_at(idx)))
+            .map_err(|_| SnapshotError::Malformed("invalid blocked tiles"))
+    }
+
+    /// Reads a 128-bit little-endian bitset.
+    fn hexo_set(&mut self, piece_set: PieceSet) -> Result<HexoSet, SnapshotError> {
+        let bitset = u128::from_le_bytes(self.take(16)?.try_into().unwrap());
+        HexoSet::from_bitset(piece_set, bitset).ok_or(SnapshotError::Malformed("invalid hexo set"))
+    }
+
+    fn piece_set(&mut self) -> Result<PieceSet, SnapshotError> {
+        let index = self.u8()?;
+        if index != CUSTOM_PIECE_SET {
+            return match index {
+                0 => Ok(PieceSet::hexominoes()),
+                1 => Ok(PieceSet::pentominoes()),
+                _ => Err(SnapshotError::Malformed("invalid piece set")),
+            };
+        }
+        let num_pieces = self.u8()?;
+        let pieces: Vec<Tiles> = (0..num_pieces)
+            .map(|_| {
+                let num_tiles = self.u8()?;
+                (0..num_tiles)
+                    .map(|_| Ok(Pos::new(self.i16()? as i32, self.i16()? as i32)))
+                    .collect()
+            })
+            .collect::<Result<_, SnapshotError>>()?;
+        PieceSet::new(pieces).map_err(|_| SnapshotError::Malformed("invalid piece set"))
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base64::encode_config(
+            self.to_bytes(),
+            base64::URL_SAFE_NO_PAD,
+        ))
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = SnapshotError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = base64::decode_config(s.trim(), base64::URL_SAFE_NO_PAD)
+            .map_err(|_| SnapshotError::Malformed("invalid base64"))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Action, State};
+    use assert2::{assert, check, let_assert};
+
+    fn state_in_place_phase() -> State {
+        let mut state = State::with_board_spec(BoardSpec::new(4, 6).unwrap());
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        state
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_bytes_and_text() {
+        let long_board = State::with_board_spec(BoardSpec::new(1, BoardSpec::MAX_SIDE).unwrap());
+        for state in [State::new(), state_in_place_phase(), long_board] {
+            let snapshot = state.snapshot();
+            check!(Snapshot::from_bytes(&snapshot.to_bytes()) == Ok(snapshot.clone()));
+            check!(snapshot.to_string().parse::<Snapshot>() == Ok(snapshot.clone()));
+            let_assert!(Ok(restored) = State::from_snapshot(snapshot));
+            check!(restored.zobrist_hash128() == state.zobrist_hash128());
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_blocked_tiles() {
+        let layout: BoardLayout = "....../.#..#./....../......".parse().unwrap();
+        let snapshot = State::with_layout(layout).snapshot();
+        let_assert!(Ok(restored) = Snapshot::from_bytes(&snapshot.to_bytes()));
+        check!(restored.board.layout() == layout);
+        check!(restored == snapshot);
+    }
+
+    #[test]
+    fn snapshot_keeps_piece_set() {
+        let piece_set = PieceSet::new(vec![
+            vec![Pos::new(0, 0), Pos::new(0, 1), Pos::new(0, 2)],
+            vec![Pos::new(0, 0), Pos::new(0, 1), Pos::new(1, 1)],
+        ])
+        .unwrap();
+        for piece_set in [PieceSet::pentominoes(), piece_set] {
+            let mut state = State::with_piece_set(BoardSpec::new(4, 6).unwrap(), piece_set);
+            let hexo = piece_set.hexo(1).unwrap();
+            assert!(let Ok(_) = state.current_player_play(Action::Pick(hexo)));
+            let snapshot = state.snapshot();
+            let_assert!(Ok(restored) = Snapshot::from_bytes(&snapshot.to_bytes()));
+            check!(restored == snapshot);
+            check!(State::from_snapshot(restored).map(|state| state.piece_set()) == Ok(piece_set));
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_rules_and_pending_pick() {
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        for draft in [DraftRule::RandomDeal { seed: 7 }, DraftRule::Blind] {
+            let rules = Rules {
+                draft,
+                bans_per_player: 1,
+                ..Rules::default()
+            };
+            let_assert!(Ok(mut state) = State::with_rules(BoardSpec::new(5, 6).unwrap(), pool.clone(), rules));
+            for id in [3, 4, 5] {
+                let hexo = PieceSet::pentominoes().hexo(id).unwrap();
+                let _ = state.current_player_play(Action::Ban(hexo));
+                let _ = state.current_player_play(Action::Pick(hexo));
+            }
+            let snapshot = state.snapshot();
+            let_assert!(Ok(restored) = Snapshot::from_bytes(&snapshot.to_bytes()));
+            check!(restored == snapshot);
+            let_assert!(Ok(restored) = State::from_snapshot(restored));
+            check!(restored.zobrist_hash128() == state.zobrist_hash128());
+            let json = serde_json::to_string(&state).unwrap();
+            let_assert!(Ok(restored) = serde_json::from_str::<State>(&json));
+            check!(restored == state);
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_players_and_elimination_order() {
+        let rules = Rules {
+            num_players: 4,
+            ..Rules::default()
+        };
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        let_assert!(Ok(mut state) = State::with_rules(BoardSpec::new(5, 6).unwrap(), pool, rules));
+        while state.phase() != GamePhase::End {
+            let action = state.legal_actions().next().unwrap();
+            assert!(let Ok(_) = state.current_player_play(action));
+        }
+        check!(state.eliminated().len() == 3);
+        let snapshot = state.snapshot();
+        let_assert!(Ok(restored) = Snapshot::from_bytes(&snapshot.to_bytes()));
+        check!(restored == snapshot);
+        let_assert!(Ok(restored) = State::from_snapshot(restored));
+        check!(restored.eliminated() == state.eliminated());
+        check!(restored.zobrist_hash128() == state.zobrist_hash128());
+        let json = serde_json::to_string(&state).unwrap();
+        let_assert!(Ok(restored) = serde_json::from_str::<State>(&json));
+        check!(restored == state);
+    }
+
+    #[test]
+    fn state_round_trips_through_serde() {
+        let mut state = state_in_place_phase();
+        state.undo();
+        let json = serde_json::to_string(&state).unwrap();
+        let_assert!(Ok(restored) = serde_json::from_str::<State>(&json));
+        check!(restored == state);
+        check!(restored.zobrist_hash128() == state.zobrist_hash128());
+    }
+
+    #[test]
+    fn invalid_snapshots_are_rejected() {
+        let bytes = State::new().snapshot().to_bytes();
+        check!(Snapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut version = bytes.clone();
+        version[MAGIC.len()] = 2;
+        check!(Snapshot::from_bytes(&version) == Err(SnapshotError::UnsupportedVersion(2)));
+        let mut snapshot = State::new().snapshot();
+        snapshot.version = 2;
+        check!(State::from_snapshot(snapshot) == Err(SnapshotError::UnsupportedVersion(2)));
+        let mut duplicate = bytes.clone();
+        // Skip the version, board size, blocked tiles, piece set, phase, player, number of
+        // players and remaining hexos.
+        duplicate[MAGIC.len() + 3 + 27 + 4 + 16] = 1;
+        check!(Snapshot::from_bytes(&duplicate) == Err(SnapshotError::DuplicateHexo(Hexo::new(0))));
+        check!("not a snapshot".parse::<Snapshot>().is_err());
+
+        let mut snapshot = state_in_place_phase().snapshot();
+        snapshot.phase = GamePhase::Pick;
+        check!(State::from_snapshot(snapshot).is_err());
+
+        let mut json = serde_json::to_value(state_in_place_phase()).unwrap();
+        json["history"].as_array_mut().unwrap().pop();
+        check!(serde_json::from_value::<State>(json).is_err());
+    }
+}
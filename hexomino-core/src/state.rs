@@ -1,25 +1,63 @@
 use super::{
     board::Board,
+    board_layout::BoardLayout,
+    board_spec::BoardSpec,
     hexo::{Hexo, HexoSet, MovedHexo, PlacedHexo},
+    piece_set::PieceSet,
+    pos::Pos,
+    snapshot::{Snapshot, SnapshotError},
+    zobrist::{self, SplitMix64},
 };
 use getset::{CopyGetters, Getters};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     #[error("{0:?} is not a valid hexo")]
     NotValidHexo(Hexo),
     #[error("action {action:?} is invalid during phase {phase:?}")]
     NotValidAction { action: Action, phase: GamePhase },
-    #[error("it is not the turn of player({player:?})")]
-    NotInTurn { player: Player },
-    #[error("hexo {moved_hexo:?} cannot be placed")]
-    CannotPlaceHexo { moved_hexo: MovedHexo },
+    #[error("it is not the turn of {seat:?}")]
+    NotInTurn { seat: Seat },
+    #[error("hexo {moved_hexo:?} has tiles {tiles:?} outside the board")]
+    HexoOutOfBounds {
+        moved_hexo: MovedHexo,
+        tiles: Vec<Pos>,
+    },
+    #[error("hexo {moved_hexo:?} has tiles {tiles:?} on blocked tiles")]
+    HexoOnBlockedTiles {
+        moved_hexo: MovedHexo,
+        tiles: Vec<Pos>,
+    },
+    #[error("hexo {moved_hexo:?} overlaps with placed hexos at {overlaps:?}")]
+    HexoOverlaps {
+        moved_hexo: MovedHexo,
+        overlaps: Vec<(Pos, Seat)>,
+    },
+    #[error("hexo {hexo:?} is not in the inventory of {seat:?}")]
+    HexoNotInInventory { hexo: Hexo, seat: Seat },
+    #[error("a game needs at least one hexo to pick")]
+    EmptyPool,
+    #[error("cannot ban {bans} hexos and still have one to pick from a pool of {pool}")]
+    TooManyBans { bans: usize, pool: usize },
+    #[error("the rules do not support {0} players")]
+    UnsupportedPlayerCount(usize),
 }
 pub(crate) type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// An error when replaying a list of actions, with the first action that cannot be played.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("action #{index} ({action}) cannot be played: {error}")]
+pub struct ReplayError {
+    /// The index of the action in the list.
+    pub index: usize,
+    pub action: Action,
+    pub error: Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Player {
     First = 0,
     Second = 1,
@@ -38,50 +76,238 @@ impl Player {
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Getters)]
+/// A place in the turn order of a game, starting from 0.
+///
+/// The first two seats are those of [`Player::First`] and [`Player::Second`]. A game of more
+/// than two players has a seat for each player after them, which has no [`Player`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "SeatRepr", into = "SeatRepr")]
+pub struct Seat(u8);
+
+/// The serialized form of [`Seat`], which matches that of [`Player`] for the first two seats.
+#[derive(Serialize, Deserialize)]
+enum SeatRepr {
+    First,
+    Second,
+    Third,
+    Fourth,
+}
+
+impl From<SeatRepr> for Seat {
+    fn from(repr: SeatRepr) -> Self {
+        Self(repr as u8)
+    }
+}
+
+impl From<Seat> for SeatRepr {
+    fn from(seat: Seat) -> Self {
+        use SeatRepr::*;
+        [First, Second, Third, Fourth]
+            .into_iter()
+            .nth(seat.index())
+            .unwrap()
+    }
+}
+
+impl Seat {
+    /// The most players a game can have.
+    pub const MAX: usize = 4;
+
+    /// Returns the seat with the index, if it is less than [`Seat::MAX`].
+    pub fn new(index: usize) -> Option<Seat> {
+        (index < Self::MAX).then_some(Self(index as u8))
+    }
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+    /// Returns the player of the seat, if it is one of the first two.
+    pub fn player(self) -> Option<Player> {
+        match self.0 {
+            0 => Some(Player::First),
+            1 => Some(Player::Second),
+            _ => None,
+        }
+    }
+    /// Returns the seat after this one in turn order, in a game of `num_players` players.
+    pub fn next(self, num_players: usize) -> Seat {
+        Self(((self.index() + 1) % num_players) as u8)
+    }
+    /// Returns the seats of a game of `num_players` players, in turn order.
+    pub(crate) fn all(num_players: usize) -> impl Iterator<Item = Seat> + Clone {
+        (0..num_players.min(Self::MAX)).map(|index| Self(index as u8))
+    }
+}
+
+impl From<Player> for Seat {
+    fn from(player: Player) -> Self {
+        Self(player as u8)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Getters, Serialize, Deserialize)]
+#[serde(try_from = "InventoryRepr", into = "InventoryRepr")]
 pub struct Inventory {
     #[getset(get = "pub")]
     remaining_hexos: HexoSet,
-    player_hexos: [HexoSet; 2],
+    /// The hexos of each player in the game, indexed by [`Seat::index`].
+    player_hexos: Vec<HexoSet>,
+    /// The hexos banned before the draft, which nobody can pick.
+    #[getset(get = "pub")]
+    banned_hexos: HexoSet,
+    zobrist_hash: u128,
 }
 
-impl Inventory {
-    fn new() -> Self {
+/// The serialized form of [`Inventory`], from which the rest is recomputed.
+#[derive(Serialize, Deserialize)]
+struct InventoryRepr {
+    remaining_hexos: HexoSet,
+    player_hexos: Vec<HexoSet>,
+    #[serde(default)]
+    banned_hexos: Option<HexoSet>,
+}
+
+impl From<Inventory> for InventoryRepr {
+    fn from(inventory: Inventory) -> Self {
         Self {
-            remaining_hexos: HexoSet::all(),
-            player_hexos: [HexoSet::empty(), HexoSet::empty()],
+            remaining_hexos: inventory.remaining_hexos,
+            player_hexos: inventory.player_hexos,
+            banned_hexos: Some(inventory.banned_hexos),
         }
     }
-    fn add(&mut self, player: Player, hexo: Hexo) -> Result<()> {
+}
+
+impl TryFrom<InventoryRepr> for Inventory {
+    type Error = SnapshotError;
+
+    fn try_from(repr: InventoryRepr) -> std::result::Result<Self, Self::Error> {
+        let banned_hexos = repr
+            .banned_hexos
+            .unwrap_or_else(|| HexoSet::empty(repr.remaining_hexos.piece_set()));
+        Self::from_sets(repr.remaining_hexos, repr.player_hexos, banned_hexos)
+    }
+}
+
+impl Inventory {
+    fn new(pool: HexoSet, num_players: usize) -> Self {
+        let empty = HexoSet::empty(pool.piece_set());
+        Self::from_sets(pool, vec![empty.clone(); num_players], empty).unwrap()
+    }
+    /// Creates an inventory from disjoint sets of hexos of the same piece set, with a set for
+    /// each player.
+    pub(crate) fn from_sets(
+        remaining_hexos: HexoSet,
+        player_hexos: Vec<HexoSet>,
+        banned_hexos: HexoSet,
+    ) -> std::result::Result<Self, SnapshotError> {
+        if !(2..=Seat::MAX).contains(&player_hexos.len()) {
+            return Err(SnapshotError::InvalidPlayerCount(player_hexos.len()));
+        }
+        let piece_set = remaining_hexos.piece_set();
+        if player_hexos
+            .iter()
+            .chain([&banned_hexos])
+            .any(|hexos| hexos.piece_set() != piece_set)
+        {
+            return Err(SnapshotError::PieceSetMismatch);
+        }
+        let mut inventory = Self {
+            zobrist_hash: remaining_hexos
+                .iter()
+                .fold(0, |hash, hexo| hash ^ zobrist::remaining_hexo_key(hexo)),
+            remaining_hexos,
+            player_hexos: vec![HexoSet::empty(piece_set); player_hexos.len()],
+            banned_hexos: HexoSet::empty(piece_set),
+        };
+        for hexo in banned_hexos.iter() {
+            if inventory.has(hexo) {
+                return Err(SnapshotError::DuplicateHexo(hexo));
+            }
+            inventory.banned_hexos.add(hexo);
+        }
+        for (seat, hexos) in Seat::all(player_hexos.len()).zip(player_hexos) {
+            for hexo in hexos.iter() {
+                if inventory.has(hexo) {
+                    return Err(SnapshotError::DuplicateHexo(hexo));
+                }
+                inventory.unremove(seat, hexo);
+            }
+        }
+        Ok(inventory)
+    }
+    /// Returns the set of the hexos in the inventory.
+    pub fn piece_set(&self) -> PieceSet {
+        self.remaining_hexos.piece_set()
+    }
+    /// Returns the number of players in the game.
+    pub fn num_players(&self) -> usize {
+        self.player_hexos.len()
+    }
+    /// Returns the seats of the players in the game, in turn order.
+    pub fn seats(&self) -> impl Iterator<Item = Seat> {
+        Seat::all(self.num_players())
+    }
+    /// Returns true if the hexo is remaining, owned by a player, or banned.
+    fn has(&self, hexo: Hexo) -> bool {
+        self.remaining_hexos.has(hexo)
+            || self.seat_of(hexo).is_some()
+            || self.banned_hexos.has(hexo)
+    }
+    /// Returns the number of hexos the players own.
+    fn num_owned(&self) -> usize {
+        self.player_hexos.iter().map(HexoSet::len).sum()
+    }
+    fn ban(&mut self, hexo: Hexo) -> Result<()> {
+        if !self.remaining_hexos.has(hexo) {
+            return Err(Error::NotValidHexo(hexo));
+        }
+        self.remaining_hexos.remove(hexo);
+        self.banned_hexos.add(hexo);
+        self.zobrist_hash ^= zobrist::remaining_hexo_key(hexo);
+        Ok(())
+    }
+    fn add(&mut self, seat: Seat, hexo: Hexo) -> Result<()> {
         if !self.remaining_hexos.has(hexo) {
             return Err(Error::NotValidHexo(hexo));
         }
         self.remaining_hexos.remove(hexo);
-        self.player_hexos[player.id()].add(hexo);
+        self.player_hexos[seat.index()].add(hexo);
+        self.zobrist_hash ^=
+            zobrist::remaining_hexo_key(hexo) ^ zobrist::player_hexo_key(seat, hexo);
         Ok(())
     }
-    fn remove(&mut self, player: Player, hexo: Hexo) -> Result<()> {
-        let current_player_hexos = &mut self.player_hexos[player.id()];
+    fn remove(&mut self, seat: Seat, hexo: Hexo) -> Result<()> {
+        let current_player_hexos = &mut self.player_hexos[seat.index()];
         if !current_player_hexos.has(hexo) {
             return Err(Error::NotValidHexo(hexo));
         }
         current_player_hexos.remove(hexo);
+        self.zobrist_hash ^= zobrist::player_hexo_key(seat, hexo);
         Ok(())
     }
-    pub fn hexos_of(&self, player: Player) -> &HexoSet {
-        &self.player_hexos[player.id()]
+    /// Reverts [`Inventory::remove`].
+    fn unremove(&mut self, seat: Seat, hexo: Hexo) {
+        self.player_hexos[seat.index()].add(hexo);
+        self.zobrist_hash ^= zobrist::player_hexo_key(seat, hexo);
+    }
+    /// Returns the hexos the player in the seat owns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the seat is not in the game.
+    pub fn hexos_of(&self, seat: impl Into<Seat>) -> &HexoSet {
+        &self.player_hexos[seat.into().index()]
     }
+    /// Returns the player who owns the hexo, if it is owned by one of the first two seats.
     pub fn owner_of(&self, hexo: Hexo) -> Option<Player> {
-        for player in [Player::First, Player::Second] {
-            if self.hexos_of(player).has(hexo) {
-                return Some(player);
-            }
-        }
-        None
+        self.seat_of(hexo).and_then(Seat::player)
+    }
+    /// Returns the seat of the player who owns the hexo, if there is any.
+    pub fn seat_of(&self, hexo: Hexo) -> Option<Seat> {
+        self.seats().find(|&seat| self.hexos_of(seat).has(hexo))
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GamePhase {
     Pick,
     Place,
@@ -90,28 +316,163 @@ pub enum GamePhase {
 
 use GamePhase::*;
 
-#[derive(Getters, CopyGetters, Clone)]
+/// How the hexos in the pool are distributed to the players during [`GamePhase::Pick`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DraftRule {
+    /// The players pick one hexo in turns, starting from the first player.
+    #[default]
+    Alternate,
+    /// The players pick in turns, and the turn order is reversed after everyone picks, i.e. in
+    /// the order 1-2-2-1-1-2-2-... for two players, or 1-2-3-3-2-1-1-... for three players.
+    Snake,
+    /// The hexos are shuffled with the seed and dealt in turns, starting from the first player.
+    /// Nobody picks, so the game goes to [`GamePhase::Place`] right after the bans.
+    RandomDeal { seed: u64 },
+    /// In each round, both players choose a hexo without seeing the choice of the other, the
+    /// first player choosing first. If the choices differ, both players get their hexos.
+    /// Otherwise, the player with priority gets it, and the other player picks again. The first
+    /// player has priority in the first round, and the priority alternates every round.
+    ///
+    /// The pending choice of the first player is part of the [`State`], so it should be hidden
+    /// from the second player before it is resolved. This rule is only for two players.
+    Blind,
+}
+
+/// How the winner is decided during [`GamePhase::Place`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WinRule {
+    /// The current player is eliminated when they cannot place a hexo, and the last player left
+    /// wins. In a two-player game, the game ends as soon as one player cannot place.
+    #[default]
+    Normal,
+    /// The game ends when the current player cannot place a hexo, and they win.
+    Misere,
+    /// A player who cannot place a hexo passes for the rest of the game, and the game ends when
+    /// no player can place one. The player whose placed hexos cover the most tiles wins, and the
+    /// game is a tie if more than one player covers the most.
+    AreaScoring,
+}
+
+/// The rules of a game other than the board and the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// The number of players, from 2 to [`Seat::MAX`]. The default is 2.
+    pub num_players: usize,
+    pub draft: DraftRule,
+    pub win: WinRule,
+    /// The number of hexos each player bans with [`Action::Ban`] before the draft, in turns
+    /// starting from the first player. A banned hexo is out of the game.
+    pub bans_per_player: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            num_players: 2,
+            draft: DraftRule::default(),
+            win: WinRule::default(),
+            bans_per_player: 0,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns the number of bans in a game.
+    pub fn num_bans(&self) -> usize {
+        self.num_players * self.bans_per_player
+    }
+
+    /// Returns the seats of the players in the game, in turn order.
+    pub fn seats(&self) -> impl Iterator<Item = Seat> {
+        Seat::all(self.num_players)
+    }
+}
+
+/// An action played, along with the turn it was played in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Turn {
+    action: Action,
+    phase: GamePhase,
+    #[serde(rename = "player")]
+    seat: Seat,
+    /// How the draft was before an action of the pick phase, which is restored on undo.
+    ///
+    /// This is not serialized, but recomputed by replaying the actions.
+    #[serde(skip)]
+    draft_before: Option<Box<DraftProgress>>,
+    /// The number of eliminated players before the action, which is restored on undo.
+    #[serde(skip)]
+    num_eliminated: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DraftProgress {
+    inventory: Inventory,
+    pending_pick: Option<Hexo>,
+}
+
+#[derive(Getters, CopyGetters, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "StateRepr", into = "StateRepr")]
 pub struct State {
     #[getset(get_copy = "pub")]
     phase: GamePhase,
-    current_player: Player,
+    current_seat: Seat,
+    #[getset(get_copy = "pub")]
+    rules: Rules,
     inventory: Inventory,
+    /// The choice of the first player in a round of [`DraftRule::Blind`], before it is resolved.
+    pending_pick: Option<Hexo>,
+    /// The winner after the game ends, or `None` for a tie.
+    winner: Option<Seat>,
+    /// The players who could not place a hexo on their turn, in the order they did.
+    eliminated: Vec<Seat>,
     board: Board,
+    history: Vec<Turn>,
+    undone_actions: Vec<Action>,
 }
 
 impl State {
+    /// Returns the player to move, if the game has not ended and the player is in one of the
+    /// first two seats. See [`State::current_seat`] for games of more than two players.
     pub fn current_player(&self) -> Option<Player> {
+        self.current_seat().and_then(Seat::player)
+    }
+
+    /// Returns the seat of the player to move, if the game has not ended.
+    pub fn current_seat(&self) -> Option<Seat> {
         match self.phase {
             GamePhase::End => None,
-            _ => Some(self.current_player),
+            _ => Some(self.current_seat),
         }
     }
 
+    /// Returns the winner after the game ends, if the winner is in one of the first two seats.
+    /// See [`State::winning_seat`] for games of more than two players.
     pub fn winner(&self) -> Option<Player> {
-        match self.phase {
-            GamePhase::End => Some(self.current_player.other()),
-            _ => None,
-        }
+        self.winner.and_then(Seat::player)
+    }
+
+    /// Returns the seat of the winner after the game ends.
+    ///
+    /// This is `None` before the game ends, or if the game ends in a tie, which can only happen
+    /// with [`WinRule::AreaScoring`].
+    pub fn winning_seat(&self) -> Option<Seat> {
+        self.winner
+    }
+
+    /// Returns the players who could not place a hexo on their turn, in the order they did.
+    ///
+    /// With [`WinRule::Normal`], these are the players eliminated from the game, so the order
+    /// ranks the players from the last place up. With [`WinRule::AreaScoring`], these are the
+    /// players who passed for the rest of the game.
+    pub fn eliminated(&self) -> &[Seat] {
+        &self.eliminated
+    }
+
+    /// Returns the number of players in the game.
+    pub fn num_players(&self) -> usize {
+        self.rules.num_players
     }
 
     pub fn inventory(&self) -> &Inventory {
@@ -121,59 +482,418 @@ impl State {
     pub fn board(&self) -> &Board {
         &self.board
     }
+
+    /// Returns the set of the pieces the game is played with.
+    pub fn piece_set(&self) -> PieceSet {
+        self.board.piece_set()
+    }
+
+    /// Returns a 64-bit Zobrist hash of the position.
+    ///
+    /// The hash covers the occupied tiles, the hexos each player owns, the remaining hexos, the
+    /// phase and the player to move. It does not depend on the order the actions were played.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist_hash128() as u64
+    }
+
+    /// Returns a 128-bit Zobrist hash of the position. See [`State::zobrist_hash`].
+    pub fn zobrist_hash128(&self) -> u128 {
+        self.board.zobrist_hash() ^ self.non_board_zobrist_hash()
+    }
+
+    /// Returns a 64-bit Zobrist hash of the position which is the same for positions whose boards
+    /// are mirror images of each other.
+    pub fn symmetric_zobrist_hash(&self) -> u64 {
+        self.symmetric_zobrist_hash128() as u64
+    }
+
+    /// Returns a 128-bit Zobrist hash of the position which is the same for positions whose boards
+    /// are mirror images of each other. See [`State::symmetric_zobrist_hash`].
+    pub fn symmetric_zobrist_hash128(&self) -> u128 {
+        self.board.symmetric_zobrist_hash() ^ self.non_board_zobrist_hash()
+    }
+
+    fn non_board_zobrist_hash(&self) -> u128 {
+        let pending_key = self.pending_pick.map_or(0, zobrist::pending_pick_key);
+        let eliminated_key = self
+            .eliminated
+            .iter()
+            .fold(0, |hash, &seat| hash ^ zobrist::eliminated_player_key(seat));
+        self.inventory.zobrist_hash
+            ^ zobrist::turn_key(self.phase, self.current_seat)
+            ^ pending_key
+            ^ eliminated_key
+    }
+
+    /// Returns true if the players are still banning hexos before the draft.
+    pub fn is_banning(&self) -> bool {
+        self.phase == Pick && self.inventory.banned_hexos.len() < self.rules.num_bans()
+    }
+
+    /// Returns the hexo the first player has chosen in the current round of
+    /// [`DraftRule::Blind`], which the second player should not see.
+    pub fn pending_pick(&self) -> Option<Hexo> {
+        self.pending_pick
+    }
+
+    /// Returns all the legal actions of the current player.
+    ///
+    /// During [`GamePhase::Pick`], these are the bans or the picks of every remaining hexo.
+    /// During [`GamePhase::Place`], these are every distinct placement of the hexos the current
+    /// player owns. There is no legal action after the game ends.
+    pub fn legal_actions(&self) -> impl Iterator<Item = Action> + '_ {
+        let draft_action = if self.is_banning() {
+            Action::Ban
+        } else {
+            Action::Pick
+        };
+        let picks = (self.phase == Pick).then(|| self.inventory.remaining_hexos.iter());
+        let places = (self.phase == Place).then(|| {
+            self.inventory
+                .hexos_of(self.current_seat)
+                .iter()
+                .flat_map(|hexo| self.board.placements(hexo))
+        });
+        picks
+            .into_iter()
+            .flatten()
+            .map(draft_action)
+            .chain(places.into_iter().flatten().map(Action::Place))
+    }
+
+    /// Returns the number of legal actions of the current player.
+    ///
+    /// This is the same as `self.legal_actions().count()`, but faster.
+    pub fn num_legal_actions(&self) -> usize {
+        match self.phase {
+            Pick => self.inventory.remaining_hexos.len(),
+            Place => self
+                .inventory
+                .hexos_of(self.current_seat)
+                .iter()
+                .map(|hexo| self.board.num_placements(hexo))
+                .sum(),
+            End => 0,
+        }
+    }
 }
 
-impl State {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
+/// The serialized form of [`State`]: the current position and how it is reached.
+#[derive(Serialize, Deserialize)]
+struct StateRepr {
+    snapshot: Snapshot,
+    history: Vec<Turn>,
+    undone_actions: Vec<Action>,
+}
+
+impl From<State> for StateRepr {
+    fn from(state: State) -> Self {
         Self {
-            phase: GamePhase::Pick,
-            current_player: Player::First,
-            inventory: Inventory::new(),
-            board: Board::new(),
+            snapshot: state.snapshot(),
+            history: state.history,
+            undone_actions: state.undone_actions,
         }
     }
+}
 
-    pub fn new_random_place() -> Self {
-        let mut state = State::new();
-        for hexo in Hexo::all_hexos() {
-            state
-                .play(state.current_player, Action::Pick(hexo))
-                .unwrap();
+impl TryFrom<StateRepr> for State {
+    type Error = SnapshotError;
+
+    fn try_from(repr: StateRepr) -> std::result::Result<Self, Self::Error> {
+        let target = Self::from_snapshot(repr.snapshot)?;
+        let mut state = target.replay_history(&repr.history)?;
+        state.undone_actions = repr.undone_actions;
+        let mut redone = state.clone();
+        while let Some(action) = redone.undone_actions.pop() {
+            redone
+                .apply(action)
+                .map_err(|_| SnapshotError::InvalidHistory)?;
         }
-        state.current_player = Player::First;
+        Ok(state)
+    }
+}
 
-        state
+impl State {
+    /// Returns the current position, without the history.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            version: Snapshot::VERSION,
+            phase: self.phase,
+            current_seat: self.current_seat,
+            rules: self.rules,
+            inventory: self.inventory.clone(),
+            pending_pick: self.pending_pick,
+            winner: self.winner,
+            eliminated: self.eliminated.clone(),
+            board: self.board.clone(),
+        }
     }
 
-    pub fn new_random_end() -> Self {
-        let mut state = State::new_random_place();
-        while state.phase() != GamePhase::End {
-            let current_player = state.current_player().unwrap();
-            let hexos = state.inventory().hexos_of(current_player);
-            for hexo in hexos.clone().iter() {
-                if let Some(moved_hexo) = state.board.try_find_placement(hexo) {
-                    state
-                        .play(state.current_player, Action::Place(moved_hexo))
-                        .unwrap();
-                    break;
+    /// Creates a state at the position of the snapshot, with an empty history.
+    ///
+    /// No hexo can be remaining, owned by a player, banned, or placed on the board more than
+    /// once, and the position must be consistent with the phase and the rules. The hexos that
+    /// appear form the pool of the game.
+    pub fn from_snapshot(snapshot: Snapshot) -> std::result::Result<Self, SnapshotError> {
+        if snapshot.version != Snapshot::VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        let Snapshot {
+            phase,
+            current_seat,
+            rules,
+            inventory,
+            pending_pick,
+            winner,
+            eliminated,
+            board,
+            version: _,
+        } = snapshot;
+        let piece_set = board.piece_set();
+        if inventory.piece_set() != piece_set {
+            return Err(SnapshotError::PieceSetMismatch);
+        }
+        let num_players = inventory.num_players();
+        if num_players != rules.num_players {
+            return Err(SnapshotError::InvalidPlayerCount(rules.num_players));
+        }
+        let in_game = |seat: Seat| seat.index() < num_players;
+        let players_in_game = in_game(current_seat)
+            && winner.map_or(true, in_game)
+            && eliminated.iter().all(|&seat| in_game(seat))
+            && eliminated.iter().all_unique()
+            && board
+                .placed_hexos()
+                .iter()
+                .all(|placed_hexo| in_game(placed_hexo.seat()));
+        if !players_in_game {
+            return Err(SnapshotError::Malformed("a player is not in the game"));
+        }
+        let mut placed = HexoSet::empty(piece_set);
+        for placed_hexo in board.placed_hexos() {
+            let hexo = placed_hexo.moved_hexo().hexo();
+            if inventory.has(hexo) || placed.has(hexo) {
+                return Err(SnapshotError::DuplicateHexo(hexo));
+            }
+            placed.add(hexo);
+        }
+        let state = Self {
+            phase,
+            current_seat,
+            rules,
+            inventory,
+            pending_pick,
+            winner,
+            eliminated,
+            board,
+            history: vec![],
+            undone_actions: vec![],
+        };
+        if winner.is_some() && phase != End {
+            return Err(SnapshotError::InconsistentPhase(phase));
+        }
+        let num_banned = state.inventory.banned_hexos.len();
+        let bans_done = num_banned == rules.num_bans();
+        let pending_valid = pending_pick.map_or(true, |hexo| {
+            rules.draft == DraftRule::Blind && state.inventory.remaining_hexos.has(hexo)
+        });
+        let consistent = num_banned <= rules.num_bans()
+            && pending_valid
+            && match phase {
+                Pick => {
+                    state.eliminated.is_empty()
+                        && !state.inventory.remaining_hexos.is_empty()
+                        && state.board.placed_hexos().is_empty()
+                        && (bans_done || state.inventory.num_owned() == 0)
+                        && !(bans_done && matches!(rules.draft, DraftRule::RandomDeal { .. }))
+                        && current_seat == state.drafting_seat()
+                }
+                Place => {
+                    bans_done
+                        && state.inventory.remaining_hexos.is_empty()
+                        && !state.eliminated.contains(&current_seat)
+                        && state.current_player_can_place()
                 }
+                End => bans_done && state.inventory.remaining_hexos.is_empty(),
+            };
+        if !consistent {
+            return Err(SnapshotError::InconsistentPhase(phase));
+        }
+        Ok(state)
+    }
+
+    /// Plays the history from the initial state of the game, and returns the result if it is
+    /// at the same position as `self`.
+    ///
+    /// A game that ends before it would by the rules is one that [`State::set_winner`] ended.
+    fn replay_history(&self, history: &[Turn]) -> std::result::Result<Self, SnapshotError> {
+        let mut state = State::with_rules(self.board.layout(), self.pool(), self.rules)
+            .map_err(|_| SnapshotError::InvalidHistory)?;
+        for turn in history {
+            if state.phase != turn.phase || state.current_seat != turn.seat {
+                return Err(SnapshotError::InvalidHistory);
             }
+            state
+                .apply(turn.action)
+                .map_err(|_| SnapshotError::InvalidHistory)?;
         }
+        if self.phase == End && state.phase != End {
+            let winner = self.winner.ok_or(SnapshotError::InvalidHistory)?;
+            state.set_winner(winner);
+        }
+        if state.snapshot() != self.snapshot() {
+            return Err(SnapshotError::InvalidHistory);
+        }
+        Ok(state)
+    }
+}
 
-        state
+impl State {
+    /// Creates a game on the standard board.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_board_spec(BoardSpec::default())
+    }
+
+    /// Creates a game on a board of the spec.
+    pub fn with_board_spec(spec: BoardSpec) -> Self {
+        Self::with_piece_set(spec, PieceSet::default())
+    }
+
+    /// Creates a game on a board of the layout, whose blocked tiles no hexo can cover.
+    pub fn with_layout(layout: BoardLayout) -> Self {
+        Self::with_piece_set(layout, PieceSet::default())
+    }
+
+    /// Creates a game on a board of the layout, played with the pieces of the piece set.
+    ///
+    /// The layout can be a [`BoardSpec`] for a board without blocked tiles.
+    pub fn with_piece_set(layout: impl Into<BoardLayout>, piece_set: PieceSet) -> Self {
+        Self::with_pool(layout, HexoSet::all(piece_set)).unwrap()
+    }
+
+    /// Creates a game on a board of the layout where only the hexos in the pool are picked and
+    /// placed, e.g. a hand-picked set or a [`HexoSet::sample`] of the piece set.
+    ///
+    /// The pool cannot be empty. It can have an odd number of hexos, in which case the first
+    /// player picks one more hexo.
+    pub fn with_pool(layout: impl Into<BoardLayout>, pool: HexoSet) -> Result<Self> {
+        Self::with_rules(layout, pool, Rules::default())
+    }
+
+    /// Creates a game on a board of the layout with the hexos in the pool, where the hexos are
+    /// banned and drafted by the rules.
+    ///
+    /// The game can have from 2 to [`Seat::MAX`] players, and [`DraftRule::Blind`] needs
+    /// exactly two. At least one hexo must be left to draft after the bans.
+    pub fn with_rules(layout: impl Into<BoardLayout>, pool: HexoSet, rules: Rules) -> Result<Self> {
+        let num_players = rules.num_players;
+        if !(2..=Seat::MAX).contains(&num_players)
+            || (rules.draft == DraftRule::Blind && num_players != 2)
+        {
+            return Err(Error::UnsupportedPlayerCount(num_players));
+        }
+        if pool.is_empty() {
+            return Err(Error::EmptyPool);
+        }
+        if pool.len() <= rules.num_bans() {
+            return Err(Error::TooManyBans {
+                bans: rules.num_bans(),
+                pool: pool.len(),
+            });
+        }
+        let mut state = Self {
+            phase: GamePhase::Pick,
+            current_seat: Player::First.into(),
+            rules,
+            board: Board::new(layout.into(), pool.piece_set()),
+            inventory: Inventory::new(pool, num_players),
+            pending_pick: None,
+            winner: None,
+            eliminated: vec![],
+            history: vec![],
+            undone_actions: vec![],
+        };
+        state.advance_draft();
+        Ok(state)
+    }
+
+    /// Returns the hexos in the game, which are the remaining hexos, the hexos owned by the
+    /// players, the banned hexos, and the placed hexos.
+    pub fn pool(&self) -> HexoSet {
+        let mut pool = self.inventory.remaining_hexos.clone();
+        let owned = self
+            .inventory
+            .seats()
+            .flat_map(|seat| self.inventory.hexos_of(seat).iter())
+            .chain(self.inventory.banned_hexos.iter());
+        let placed = self
+            .board
+            .placed_hexos()
+            .iter()
+            .map(|placed_hexo| placed_hexo.moved_hexo().hexo());
+        for hexo in owned.chain(placed) {
+            pool.add(hexo);
+        }
+        pool
+    }
+
+    /// Plays the actions in order from the initial state of the standard game, and returns the
+    /// final state.
+    pub fn from_actions(actions: &[Action]) -> std::result::Result<Self, ReplayError> {
+        Self::replay(Self::new(), actions)
     }
 
-    pub fn play(&mut self, player: Player, action: Action) -> Result<()> {
-        if self.current_player() != Some(player) {
-            return Err(Error::NotInTurn { player });
+    /// Plays the actions in order from the state, e.g. a game with other rules or on another
+    /// board, and returns the final state.
+    pub fn replay(initial: State, actions: &[Action]) -> std::result::Result<Self, ReplayError> {
+        let mut state = initial;
+        for (index, &action) in actions.iter().enumerate() {
+            state
+                .current_player_play(action)
+                .map_err(|error| ReplayError {
+                    index,
+                    action,
+                    error,
+                })?;
+        }
+        Ok(state)
+    }
+
+    /// Plays the action if it is the turn of the player in the seat.
+    pub fn play(&mut self, seat: impl Into<Seat>, action: Action) -> Result<()> {
+        let seat = seat.into();
+        if self.current_seat() != Some(seat) {
+            return Err(Error::NotInTurn { seat });
         }
         self.current_player_play(action)
     }
 
     pub fn current_player_play(&mut self, action: Action) -> Result<()> {
+        self.apply(action)?;
+        self.undone_actions.clear();
+        Ok(())
+    }
+
+    /// Plays the action, and keeps the undone actions.
+    pub(crate) fn apply(&mut self, action: Action) -> Result<()> {
+        let draft_before = (self.phase == Pick).then(|| {
+            Box::new(DraftProgress {
+                inventory: self.inventory.clone(),
+                pending_pick: self.pending_pick,
+            })
+        });
+        let turn = Turn {
+            action,
+            phase: self.phase,
+            seat: self.current_seat,
+            draft_before,
+            num_eliminated: self.eliminated.len(),
+        };
+        let banning = self.is_banning();
         match (self.phase, action) {
-            (GamePhase::Pick, Action::Pick(hexo)) => self.pick(hexo)?,
+            (GamePhase::Pick, Action::Ban(hexo)) if banning => self.inventory.ban(hexo)?,
+            (GamePhase::Pick, Action::Pick(hexo)) if !banning => self.pick(hexo)?,
             (GamePhase::Place, Action::Place(hexo)) => self.place(hexo)?,
             (_, _) => {
                 return Err(Error::NotValidAction {
@@ -183,25 +903,181 @@ impl State {
             }
         }
         self.next();
+        self.history.push(turn);
         Ok(())
     }
 
+    /// Returns the actions played so far, in order.
+    pub fn actions(&self) -> impl DoubleEndedIterator<Item = Action> + ExactSizeIterator + '_ {
+        self.history.iter().map(|turn| turn.action)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone_actions.is_empty()
+    }
+
+    /// Takes back the last action, and returns it.
+    ///
+    /// The state is restored to exactly how it was before the action was played, including the
+    /// phase and the current player.
+    pub fn undo(&mut self) -> Option<Action> {
+        let action = self.unapply()?;
+        self.undone_actions.push(action);
+        Some(action)
+    }
+
+    /// Takes back the last action like [`State::undo`], but does not keep it to be redone.
+    pub(crate) fn unapply(&mut self) -> Option<Action> {
+        let Turn {
+            action,
+            phase,
+            seat,
+            draft_before,
+            num_eliminated,
+        } = self.history.pop()?;
+        match (action, draft_before) {
+            (_, Some(draft)) => {
+                let DraftProgress {
+                    inventory,
+                    pending_pick,
+                } = *draft;
+                self.inventory = inventory;
+                self.pending_pick = pending_pick;
+            }
+            (Action::Place(moved_hexo), None) => {
+                self.board.unplace();
+                self.inventory.unremove(seat, moved_hexo.hexo());
+            }
+            (_, None) => unreachable!("an action of the pick phase keeps the draft before it"),
+        }
+        self.phase = phase;
+        self.current_seat = seat;
+        self.winner = None;
+        self.eliminated.truncate(num_eliminated);
+        Some(action)
+    }
+
+    /// Plays the last undone action again, and returns it.
+    ///
+    /// Playing any action other than through this function forgets all the undone actions.
+    pub fn redo(&mut self) -> Option<Action> {
+        let action = self.undone_actions.pop()?;
+        self.apply(action)
+            .expect("an undone action should be valid to play again");
+        Some(action)
+    }
+
     fn pick(&mut self, hexo: Hexo) -> Result<()> {
-        self.inventory.add(self.current_player, hexo)
+        if !self.inventory.remaining_hexos.has(hexo) {
+            return Err(Error::NotValidHexo(hexo));
+        }
+        let picked = self.inventory.num_owned();
+        match (self.rules.draft, self.pending_pick) {
+            (DraftRule::Blind, None) if picked % 2 == 0 => {
+                self.pending_pick = Some(hexo);
+                Ok(())
+            }
+            (DraftRule::Blind, Some(pending)) => {
+                self.pending_pick = None;
+                if pending != hexo {
+                    self.inventory.add(Player::First.into(), pending)?;
+                    self.inventory.add(Player::Second.into(), hexo)
+                } else {
+                    let priority = if picked / 2 % 2 == 0 {
+                        Player::First
+                    } else {
+                        Player::Second
+                    };
+                    self.inventory.add(priority.into(), hexo)
+                }
+            }
+            _ => self.inventory.add(self.current_seat, hexo),
+        }
+    }
+
+    /// Returns the seat of the player who bans or picks next during [`GamePhase::Pick`].
+    fn drafting_seat(&self) -> Seat {
+        let num_players = self.num_players();
+        let in_turn = |count: usize| Seat((count % num_players) as u8);
+        let banned = self.inventory.banned_hexos.len();
+        if banned < self.rules.num_bans() {
+            return in_turn(banned);
+        }
+        let picked = self.inventory.num_owned();
+        match self.rules.draft {
+            DraftRule::Alternate | DraftRule::RandomDeal { .. } => in_turn(picked),
+            DraftRule::Snake if picked / num_players % 2 == 0 => in_turn(picked),
+            DraftRule::Snake => in_turn(num_players - 1 - picked % num_players),
+            DraftRule::Blind if self.pending_pick.is_some() => Player::Second.into(),
+            // The player who lost a collision picks again.
+            DraftRule::Blind if picked % 2 == 1 => {
+                let first = self.inventory.hexos_of(Player::First).len();
+                let second = self.inventory.hexos_of(Player::Second).len();
+                if first < second {
+                    Player::First.into()
+                } else {
+                    Player::Second.into()
+                }
+            }
+            DraftRule::Blind => Player::First.into(),
+        }
+    }
+
+    /// Moves the draft to the next ban or pick, dealing the hexos for
+    /// [`DraftRule::RandomDeal`], or goes to [`GamePhase::Place`] if no hexo is left.
+    fn advance_draft(&mut self) {
+        if !self.is_banning() {
+            if let DraftRule::RandomDeal { seed } = self.rules.draft {
+                self.deal(seed);
+            }
+            if self.inventory.remaining_hexos.is_empty() {
+                // The last player places first, and the game can end right away if nobody
+                // can, e.g. when the second player has picked no hexo from a pool of one.
+                self.phase = GamePhase::Place;
+                self.pass_to(Seat((self.num_players() - 1) as u8));
+                return;
+            }
+        }
+        self.current_seat = self.drafting_seat();
+    }
+
+    /// Shuffles the remaining hexos with the seed, and deals them to the players in turns.
+    fn deal(&mut self, seed: u64) {
+        let mut rng = SplitMix64(seed);
+        let mut hexos: Vec<_> = self.inventory.remaining_hexos.iter().collect();
+        for i in (1..hexos.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            hexos.swap(i, j);
+        }
+        for (seat, hexo) in Seat::all(self.num_players()).cycle().zip(hexos) {
+            self.inventory.add(seat, hexo).unwrap();
+        }
     }
 
     fn place(&mut self, moved_hexo: MovedHexo) -> Result<()> {
-        if !self.board.can_place(&moved_hexo) {
-            return Err(Error::CannotPlaceHexo { moved_hexo });
+        let hexo = moved_hexo.hexo();
+        if !self.inventory.hexos_of(self.current_seat).has(hexo) {
+            return Err(Error::HexoNotInInventory {
+                hexo,
+                seat: self.current_seat,
+            });
         }
-        self.inventory
-            .remove(self.current_player, moved_hexo.hexo())?;
+        self.board.check_placement(&moved_hexo)?;
+        self.inventory.remove(self.current_seat, hexo)?;
         self.board
-            .place(PlacedHexo::new(moved_hexo, self.current_player))
+            .place(PlacedHexo::new(moved_hexo, self.current_seat))
     }
 
     fn current_player_can_place(&self) -> bool {
-        let hexos = self.inventory.hexos_of(self.current_player);
+        self.can_place(self.current_seat)
+    }
+
+    fn can_place(&self, seat: Seat) -> bool {
+        let hexos = self.inventory.hexos_of(seat);
         if hexos.is_empty() {
             return false;
         }
@@ -218,33 +1094,74 @@ impl State {
             End => {
                 panic!("The game had already ended");
             }
-            Pick => {
-                if self.inventory.remaining_hexos.is_empty() {
-                    self.phase = GamePhase::Place;
-                    self.current_player = Player::Second;
-                } else {
-                    self.current_player = self.current_player.other();
-                }
+            Pick => self.advance_draft(),
+            Place => self.pass_to(self.current_seat.next(self.num_players())),
+        }
+    }
+
+    /// Gives the turn during [`GamePhase::Place`] to the first player from `seat` in turn
+    /// order who can place a hexo, eliminating the players who cannot, or ends the game by the
+    /// rules.
+    fn pass_to(&mut self, mut seat: Seat) {
+        let num_players = self.num_players();
+        loop {
+            if self.eliminated.contains(&seat) {
+                seat = seat.next(num_players);
+                continue;
+            }
+            self.current_seat = seat;
+            if self.can_place(seat) {
+                return;
+            }
+            if self.rules.win == WinRule::Misere {
+                self.end(Some(seat));
+                return;
             }
-            Place => {
-                self.current_player = self.current_player.other();
-                if !self.current_player_can_place() {
-                    self.phase = GamePhase::End;
+            self.eliminated.push(seat);
+            match self.rules.win {
+                WinRule::Normal if self.eliminated.len() == num_players - 1 => {
+                    let winner = self
+                        .rules
+                        .seats()
+                        .find(|seat| !self.eliminated.contains(seat));
+                    self.end(winner);
+                    return;
                 }
+                WinRule::AreaScoring if self.eliminated.len() == num_players => {
+                    let areas: Vec<_> = self
+                        .rules
+                        .seats()
+                        .map(|seat| (self.board.area_of(seat), seat))
+                        .sorted()
+                        .rev()
+                        .collect();
+                    let tie = areas[0].0 == areas[1].0;
+                    self.end((!tie).then_some(areas[0].1));
+                    return;
+                }
+                _ => seat = seat.next(num_players),
             }
         }
     }
 
-    pub fn set_winner(&mut self, winner: Player) {
-        self.current_player = winner.other();
+    fn end(&mut self, winner: Option<Seat>) {
         self.phase = GamePhase::End;
+        self.winner = winner;
+    }
+
+    /// Ends the game with the winner regardless of the rules, e.g. when the other players
+    /// resign.
+    pub fn set_winner(&mut self, winner: impl Into<Seat>) {
+        self.end(Some(winner.into()));
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Action {
     Pick(Hexo),
     Place(MovedHexo),
+    /// Bans a remaining hexo before the draft. See [`Rules::bans_per_player`].
+    Ban(Hexo),
 }
 
 #[cfg(test)]
@@ -253,10 +1170,15 @@ mod tests {
 
     use super::*;
     use assert2::{assert, check, let_assert};
+    use itertools::Itertools;
+
+    fn new_state() -> State {
+        State::with_board_spec(BoardSpec::new(4, 6).unwrap())
+    }
 
     #[test]
     fn after_pick_adds_to_player_set() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
         let_assert!(GamePhase::Pick = state.phase);
         check!(state.inventory.hexos_of(Player::First).has(Hexo::new(0)));
@@ -264,7 +1186,7 @@ mod tests {
 
     #[test]
     fn after_pick_next_turn() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Some(Player::First) = state.current_player());
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         check!(state.phase == GamePhase::Pick);
@@ -273,7 +1195,7 @@ mod tests {
 
     #[test]
     fn after_pick_ends_proceeds_to_place() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
         check!(state.phase == GamePhase::Place);
@@ -282,14 +1204,14 @@ mod tests {
 
     #[test]
     fn pick_twice_returns_error() {
-        let mut game = State::new();
+        let mut game = new_state();
         assert!(let Ok(_) = game.current_player_play(Action::Pick( Hexo::new(0) )));
         check!(let Err(_) = game.current_player_play(Action::Pick( Hexo::new(0) )));
     }
 
     #[test]
     fn player_place_show_on_board() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0) )));
         assert!(let Ok(_) = state.current_player_play(Action::Place(
@@ -305,7 +1227,7 @@ mod tests {
 
     #[test]
     fn hexo_transform_flip() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(state.phase == GamePhase::Place);
@@ -322,7 +1244,7 @@ mod tests {
 
     #[test]
     fn hexo_transform_rotate() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(state.phase() == GamePhase::Place);
@@ -339,7 +1261,7 @@ mod tests {
 
     #[test]
     fn hexo_transform_flip_rotate() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(state.phase() == GamePhase::Place);
@@ -356,7 +1278,7 @@ mod tests {
 
     #[test]
     fn when_can_not_place_goes_to_end_phase() {
-        let mut state = State::new();
+        let mut state = new_state();
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(state.phase == GamePhase::Place);
@@ -369,7 +1291,7 @@ mod tests {
 
     #[test]
     fn when_run_out_of_tiles_goes_to_end_phase() {
-        let mut game = State::new();
+        let mut game = new_state();
         assert!(let Ok(_) = game.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = game.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(game.phase == GamePhase::Place);
@@ -385,9 +1307,189 @@ mod tests {
         assert!(game.winner() == Some(Player::First));
     }
 
+    #[test]
+    fn legal_actions_in_pick_phase_are_remaining_hexos() {
+        let mut state = new_state();
+        check!(state.num_legal_actions() == 2);
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        let_assert!([Action::Pick(hexo)] = state.legal_actions().collect::<Vec<_>>()[..]);
+        check!(hexo == Hexo::new(0));
+        check!(state.num_legal_actions() == 1);
+    }
+
+    #[test]
+    fn legal_actions_in_place_phase_are_distinct_placements() {
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(state.phase == GamePhase::Place);
+        // The I hexo only fits horizontally, once in each row.
+        check!(state.num_legal_actions() == 4);
+
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        // The 2x4 bounding box has 5 positions standing and 9 positions lying, and each of them
+        // fits 4 distinct orientations.
+        check!(state.num_legal_actions() == 4 * 5 + 4 * 9);
+
+        let actions = state.legal_actions().collect::<Vec<_>>();
+        check!(actions.len() == state.num_legal_actions());
+        let mut tile_sets = actions
+            .iter()
+            .map(|action| {
+                let_assert!(Action::Place(moved_hexo) = action);
+                let mut tiles = moved_hexo.tiles().collect::<Vec<_>>();
+                tiles.sort();
+                tiles
+            })
+            .collect::<Vec<_>>();
+        tile_sets.sort();
+        tile_sets.dedup();
+        check!(tile_sets.len() == actions.len());
+        for action in actions {
+            check!(let Ok(_) = state.clone().current_player_play(action));
+        }
+    }
+
+    #[test]
+    fn no_legal_actions_after_end() {
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(1) )));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick( Hexo::new(0) )));
+        assert!(let Ok(_) = state.current_player_play(Action::Place(
+            Hexo::new(0).apply(Transform::I).move_to(Pos::ZERO)
+        )));
+        assert!(state.phase == GamePhase::End);
+        check!(state.legal_actions().next().is_none());
+        check!(state.num_legal_actions() == 0);
+    }
+
+    #[test]
+    fn undo_restores_previous_state() {
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        let picked = state.clone();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        let place = Action::Place(Hexo::new(0).apply(Transform::I).move_to(Pos::ZERO));
+        assert!(let Ok(_) = state.current_player_play(place));
+        assert!(state.phase == GamePhase::End);
+
+        check!(state.undo() == Some(place));
+        check!(state.phase == GamePhase::Place);
+        check!(state.current_player() == Some(Player::Second));
+        check!(state.inventory.hexos_of(Player::Second).has(Hexo::new(0)));
+        check!(state.board.occupied().is_empty());
+        check!(state.board.placed_hexos().is_empty());
+
+        check!(state.undo() == Some(Action::Pick(Hexo::new(0))));
+        check!(state.phase == picked.phase);
+        check!(state.current_seat == picked.current_seat);
+        check!(state.inventory == picked.inventory);
+        check!(state.actions().eq(picked.actions()));
+
+        check!(state.undo() == Some(Action::Pick(Hexo::new(1))));
+        check!(state.inventory == new_state().inventory);
+        check!(state.undo() == None);
+    }
+
+    #[test]
+    fn redo_replays_undone_actions() {
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        check!(state.undo().is_some());
+        check!(state.undo().is_some());
+        check!(state.redo() == Some(Action::Pick(Hexo::new(1))));
+        check!(state.can_redo());
+        check!(state.redo() == Some(Action::Pick(Hexo::new(0))));
+        check!(!state.can_redo());
+        check!(state.phase == GamePhase::Place);
+        check!(state.actions().len() == 2);
+
+        check!(state.undo().is_some());
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        check!(!state.can_redo());
+        check!(state.redo() == None);
+    }
+
+    #[test]
+    fn zobrist_hash_is_restored_by_undo() {
+        let mut state = new_state();
+        let mut hashes = vec![state.zobrist_hash128()];
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        hashes.push(state.zobrist_hash128());
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        hashes.push(state.zobrist_hash128());
+        assert!(let Ok(_) = state.current_player_play(Action::Place(
+            Hexo::new(0).apply(Transform::new(false, 1)).move_to(Pos::new(0, 3))
+        )));
+        hashes.push(state.zobrist_hash128());
+        check!(hashes.iter().all_unique());
+
+        while state.undo().is_some() {
+            hashes.pop();
+            check!(state.zobrist_hash128() == *hashes.last().unwrap());
+        }
+    }
+
+    #[test]
+    fn symmetric_zobrist_hash_equals_for_mirrored_boards() {
+        let mut state = new_state();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        let mut top = state.clone();
+        assert!(let Ok(_) = top.current_player_play(Action::Place(
+            Hexo::new(1).apply(Transform::new(false, 1)).move_to(Pos::new(0, 0))
+        )));
+        let mut bottom = state;
+        assert!(let Ok(_) = bottom.current_player_play(Action::Place(
+            Hexo::new(1).apply(Transform::new(false, 1)).move_to(Pos::new(0, 3))
+        )));
+        check!(top.zobrist_hash() != bottom.zobrist_hash());
+        check!(top.symmetric_zobrist_hash() == bottom.symmetric_zobrist_hash());
+    }
+
+    #[test]
+    fn from_actions_reports_first_illegal_action() {
+        let place = Action::Place(Hexo::new(0).apply(Transform::I).move_to(Pos::ZERO));
+        let actions = [
+            Action::Pick(Hexo::new(1)),
+            Action::Pick(Hexo::new(0)),
+            place,
+        ];
+        let_assert!(Ok(state) = State::from_actions(&actions));
+        check!(state.actions().eq(actions));
+        check!(state.phase() == GamePhase::Place);
+
+        let actions = [
+            Action::Pick(Hexo::new(1)),
+            Action::Pick(Hexo::new(1)),
+            place,
+        ];
+        check!(
+            State::from_actions(&actions).err()
+                == Some(ReplayError {
+                    index: 1,
+                    action: Action::Pick(Hexo::new(1)),
+                    error: Error::NotValidHexo(Hexo::new(1)),
+                })
+        );
+    }
+
+    #[test]
+    fn replay_starts_from_the_given_state() {
+        let mut state = pentomino_game(DraftRule::Snake, 0);
+        draft_order(&mut state);
+        let actions: Vec<_> = state.actions().collect();
+        let initial = pentomino_game(DraftRule::Snake, 0);
+        check!(State::replay(initial, &actions).ok() == Some(state));
+        check!(State::from_actions(&actions).is_err());
+    }
+
     #[test]
     fn when_can_place_continues() {
-        let mut game = State::new();
+        let mut game = new_state();
         assert!(let Ok(_) = game.current_player_play(Action::Pick( Hexo::new(1) )));
         assert!(let Ok(_) = game.current_player_play(Action::Pick( Hexo::new(0) )));
         assert!(game.phase == GamePhase::Place);
@@ -397,4 +1499,396 @@ mod tests {
         assert!(game.phase == GamePhase::Place);
         assert!(game.winner().is_none());
     }
+
+    #[test]
+    fn game_with_pool_picks_only_from_pool() {
+        let spec = BoardSpec::new(5, 6).unwrap();
+        let pentominoes = PieceSet::pentominoes();
+        check!(State::with_pool(spec, HexoSet::empty(pentominoes)) == Err(Error::EmptyPool));
+
+        let pool = HexoSet::all(pentominoes).sample(3, 7);
+        check!(pool.len() == 3);
+        check!(HexoSet::all(pentominoes).sample(3, 7) == pool);
+        let_assert!(Ok(mut state) = State::with_pool(spec, pool.clone()));
+        check!(state.num_legal_actions() == 3);
+        let outside = pentominoes.hexos().find(|hexo| !pool.has(*hexo)).unwrap();
+        check!(
+            state.current_player_play(Action::Pick(outside)) == Err(Error::NotValidHexo(outside))
+        );
+        for hexo in pool.iter() {
+            assert!(let Ok(_) = state.current_player_play(Action::Pick(hexo)));
+        }
+        check!(state.phase() == GamePhase::Place);
+        check!(state.current_player() == Some(Player::Second));
+        check!(state.inventory().hexos_of(Player::First).len() == 2);
+        check!(state.pool() == pool);
+        let_assert!(Ok(restored) = State::from_snapshot(state.snapshot()));
+        check!(restored.pool() == pool);
+    }
+
+    #[test]
+    fn game_ends_after_pick_if_second_player_has_no_hexo() {
+        let mut pool = HexoSet::empty(PieceSet::default());
+        pool.add(Hexo::new(0));
+        let_assert!(Ok(mut state) = State::with_pool(BoardSpec::new(4, 6).unwrap(), pool));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        check!(state.phase() == GamePhase::End);
+        check!(state.winner() == Some(Player::First));
+        check!(state.undo() == Some(Action::Pick(Hexo::new(0))));
+        check!(state.phase() == GamePhase::Pick);
+    }
+
+    #[test]
+    fn hexos_cannot_cover_blocked_tiles() {
+        let layout: BoardLayout = "#...../....../....../.....#".parse().unwrap();
+        let mut state = State::with_layout(layout);
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        let blocked = Hexo::new(0).apply(Transform::I).move_to(Pos::ZERO);
+        check!(
+            state.current_player_play(Action::Place(blocked))
+                == Err(Error::HexoOnBlockedTiles {
+                    moved_hexo: blocked,
+                    tiles: vec![Pos::new(0, 0)],
+                })
+        );
+        check!(state.board().owner_at(Pos::new(0, 0)) == None);
+        check!(state
+            .board()
+            .placements(Hexo::new(0))
+            .all(|hexo| hexo.tiles().all(|tile| !layout.is_blocked(tile))));
+    }
+
+    fn pentomino_game(draft: DraftRule, bans_per_player: usize) -> State {
+        let rules = Rules {
+            draft,
+            bans_per_player,
+            ..Rules::default()
+        };
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        State::with_rules(BoardSpec::new(5, 6).unwrap(), pool, rules).unwrap()
+    }
+
+    fn pentomino(id: usize) -> Hexo {
+        PieceSet::pentominoes().hexo(id).unwrap()
+    }
+
+    /// Picks the remaining hexos in order of their ids, and returns the seat of who picked each.
+    fn draft_order(state: &mut State) -> Vec<Seat> {
+        let mut seats = vec![];
+        while state.phase() == GamePhase::Pick {
+            let hexo = state.inventory().remaining_hexos().iter().next().unwrap();
+            seats.push(state.current_seat().unwrap());
+            assert!(let Ok(_) = state.current_player_play(Action::Pick(hexo)));
+        }
+        seats
+    }
+
+    #[test]
+    fn snake_draft_picks_one_then_two_in_turns() {
+        let [f, s] = [Player::First, Player::Second].map(Seat::from);
+        let mut state = pentomino_game(DraftRule::Snake, 0);
+        check!(draft_order(&mut state) == [f, s, s, f, f, s, s, f, f, s, s, f]);
+        check!(state.phase() == GamePhase::Place);
+        check!(state.current_player() == Some(Player::Second));
+        let_assert!(
+            Ok(restored) = serde_json::from_str::<State>(&serde_json::to_string(&state).unwrap())
+        );
+        check!(restored == state);
+    }
+
+    #[test]
+    fn random_deal_deals_all_hexos_by_seed() {
+        let state = pentomino_game(DraftRule::RandomDeal { seed: 3 }, 0);
+        check!(state.phase() == GamePhase::Place);
+        check!(state.inventory().hexos_of(Player::First).len() == 6);
+        check!(state.inventory().hexos_of(Player::Second).len() == 6);
+        check!(pentomino_game(DraftRule::RandomDeal { seed: 3 }, 0) == state);
+        let other = pentomino_game(DraftRule::RandomDeal { seed: 4 }, 0);
+        check!(other.inventory() != state.inventory());
+    }
+
+    #[test]
+    fn bans_come_before_the_draft() {
+        let mut state = pentomino_game(DraftRule::Alternate, 1);
+        check!(state.is_banning());
+        check!(state
+            .legal_actions()
+            .all(|action| matches!(action, Action::Ban(_))));
+        check!(
+            state.current_player_play(Action::Pick(pentomino(0)))
+                == Err(Error::NotValidAction {
+                    action: Action::Pick(pentomino(0)),
+                    phase: GamePhase::Pick,
+                })
+        );
+        assert!(let Ok(_) = state.play(Player::First, Action::Ban(pentomino(0))));
+        assert!(let Ok(_) = state.play(Player::Second, Action::Ban(pentomino(1))));
+        check!(!state.is_banning());
+        check!(state.current_player() == Some(Player::First));
+        check!(state.num_legal_actions() == 10);
+        check!(
+            state.current_player_play(Action::Pick(pentomino(1)))
+                == Err(Error::NotValidHexo(pentomino(1)))
+        );
+        check!(state.pool().len() == 12);
+        check!(
+            State::with_rules(
+                BoardSpec::new(5, 6).unwrap(),
+                HexoSet::all(PieceSet::pentominoes()),
+                Rules {
+                    bans_per_player: 6,
+                    ..Rules::default()
+                }
+            ) == Err(Error::TooManyBans { bans: 12, pool: 12 })
+        );
+    }
+
+    #[test]
+    fn blind_picks_resolve_collisions_by_priority() {
+        let mut state = pentomino_game(DraftRule::Blind, 0);
+        // Different choices go to both players.
+        assert!(let Ok(_) = state.play(Player::First, Action::Pick(pentomino(0))));
+        check!(state.pending_pick() == Some(pentomino(0)));
+        check!(state.inventory().hexos_of(Player::First).is_empty());
+        assert!(let Ok(_) = state.play(Player::Second, Action::Pick(pentomino(1))));
+        check!(state.pending_pick() == None);
+        check!(state.inventory().hexos_of(Player::First).has(pentomino(0)));
+        check!(state.inventory().hexos_of(Player::Second).has(pentomino(1)));
+        // The second player has priority in the second round, so the first player picks again.
+        assert!(let Ok(_) = state.play(Player::First, Action::Pick(pentomino(2))));
+        assert!(let Ok(_) = state.play(Player::Second, Action::Pick(pentomino(2))));
+        check!(state.inventory().hexos_of(Player::Second).has(pentomino(2)));
+        check!(state.current_player() == Some(Player::First));
+        assert!(let Ok(_) = state.play(Player::First, Action::Pick(pentomino(3))));
+        check!(state.inventory().hexos_of(Player::First).has(pentomino(3)));
+        // The first player has priority again in the third round.
+        assert!(let Ok(_) = state.play(Player::First, Action::Pick(pentomino(4))));
+        assert!(let Ok(_) = state.play(Player::Second, Action::Pick(pentomino(4))));
+        check!(state.inventory().hexos_of(Player::First).has(pentomino(4)));
+        check!(state.current_player() == Some(Player::Second));
+    }
+
+    #[test]
+    fn undo_restores_draft_progress() {
+        let mut state = pentomino_game(DraftRule::Blind, 1);
+        let initial = state.clone();
+        for action in [
+            Action::Ban(pentomino(5)),
+            Action::Ban(pentomino(6)),
+            Action::Pick(pentomino(0)),
+            Action::Pick(pentomino(0)),
+        ] {
+            assert!(let Ok(_) = state.current_player_play(action));
+        }
+        let played = state.clone();
+        while state.undo().is_some() {}
+        check!(state.zobrist_hash128() == initial.zobrist_hash128());
+        check!(state.snapshot() == initial.snapshot());
+        while state.redo().is_some() {}
+        check!(state == played);
+
+        let mut state = pentomino_game(DraftRule::RandomDeal { seed: 3 }, 1);
+        assert!(let Ok(_) = state.current_player_play(Action::Ban(pentomino(0))));
+        assert!(let Ok(_) = state.current_player_play(Action::Ban(pentomino(1))));
+        check!(state.phase() == GamePhase::Place);
+        check!(state.undo() == Some(Action::Ban(pentomino(1))));
+        check!(state.inventory().remaining_hexos().len() == 11);
+        check!(state.current_player() == Some(Player::Second));
+    }
+
+    fn win_rule_game(win: WinRule) -> State {
+        let rules = Rules {
+            win,
+            ..Rules::default()
+        };
+        let pool = HexoSet::all(PieceSet::default());
+        let mut state = State::with_rules(BoardSpec::new(4, 6).unwrap(), pool, rules).unwrap();
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(1))));
+        assert!(let Ok(_) = state.current_player_play(Action::Pick(Hexo::new(0))));
+        // The I hexo of the first player no longer fits after this.
+        let place = Action::Place(Hexo::new(0).apply(Transform::I).move_to(Pos::ZERO));
+        assert!(let Ok(_) = state.current_player_play(place));
+        state
+    }
+
+    #[test]
+    fn misere_player_who_cannot_place_wins() {
+        let state = win_rule_game(WinRule::Misere);
+        check!(state.phase() == GamePhase::End);
+        check!(state.winner() == Some(Player::First));
+        check!(win_rule_game(WinRule::Normal).winner() == Some(Player::Second));
+        let_assert!(Ok(restored) = State::from_snapshot(state.snapshot()));
+        check!(restored.winner() == Some(Player::First));
+    }
+
+    /// Starts a game on a 2x4 board where the hexos with the ids are picked in order.
+    fn small_game(rules: Rules, ids: &[usize]) -> State {
+        let tiles = |cells: &[(i32, i32)]| cells.iter().copied().map(Pos::from).collect();
+        let piece_set = PieceSet::new(vec![
+            tiles(&[(0, 0), (1, 0), (0, 1), (1, 1)]),
+            tiles(&[(0, 0), (1, 0), (0, 1)]),
+            tiles(&[(0, 0), (1, 0), (2, 0)]),
+            tiles(&[(0, 0)]),
+            tiles(&[(0, 0), (1, 0)]),
+        ])
+        .unwrap();
+        let mut pool = HexoSet::empty(piece_set);
+        for &id in ids {
+            pool.add(piece_set.hexo(id).unwrap());
+        }
+        let mut state = State::with_rules(BoardSpec::new(2, 4).unwrap(), pool, rules).unwrap();
+        for &id in ids {
+            let hexo = piece_set.hexo(id).unwrap();
+            assert!(let Ok(_) = state.current_player_play(Action::Pick(hexo)));
+        }
+        state
+    }
+
+    fn area_scoring_game(ids: &[usize]) -> State {
+        let rules = Rules {
+            win: WinRule::AreaScoring,
+            ..Rules::default()
+        };
+        small_game(rules, ids)
+    }
+
+    fn place(state: &mut State, id: usize, pos: Pos) {
+        let hexo = state.piece_set().hexo(id).unwrap();
+        let action = Action::Place(hexo.apply(Transform::I).move_to(pos));
+        assert!(let Ok(_) = state.current_player_play(action));
+    }
+
+    #[test]
+    fn area_scoring_plays_on_until_nobody_can_place() {
+        // The first player has the L and the I, and the second player has the O and the single
+        // tile.
+        let mut state = area_scoring_game(&[1, 0, 2, 3]);
+        place(&mut state, 0, Pos::new(1, 0));
+        // Neither the L nor the I fits in the columns left, so the second player goes on.
+        check!(state.phase() == GamePhase::Place);
+        check!(state.current_player() == Some(Player::Second));
+        place(&mut state, 3, Pos::new(0, 0));
+        check!(state.phase() == GamePhase::End);
+        check!(state.winner() == Some(Player::Second));
+        check!(state.undo().is_some());
+        check!(state.winner() == None);
+        check!(state.current_player() == Some(Player::Second));
+    }
+
+    #[test]
+    fn area_scoring_winner_covers_more_tiles() {
+        // The first player has the O and the L, and the second player has the single tile and
+        // the domino.
+        let mut state = area_scoring_game(&[0, 3, 1, 4]);
+        place(&mut state, 3, Pos::new(0, 0));
+        place(&mut state, 0, Pos::new(2, 0));
+        place(&mut state, 4, Pos::new(0, 1));
+        // The first player could not place the L first, but covers 4 tiles against 3.
+        check!(state.phase() == GamePhase::End);
+        check!(state.winner() == Some(Player::First));
+        check!(state.board().area_of(Player::First) == 4);
+        check!(state.board().area_of(Player::Second) == 3);
+    }
+
+    #[test]
+    fn area_scoring_ends_in_tie_with_equal_areas() {
+        // The first player has the I, and the second player has the L.
+        let mut state = area_scoring_game(&[2, 1]);
+        place(&mut state, 1, Pos::new(0, 0));
+        place(&mut state, 2, Pos::new(1, 1));
+        check!(state.phase() == GamePhase::End);
+        check!(state.winner() == None);
+        let_assert!(Ok(restored) = State::from_snapshot(state.snapshot()));
+        check!(restored.winner() == None);
+    }
+
+    #[test]
+    fn three_player_snake_draft_reverses_after_everyone_picks() {
+        let rules = Rules {
+            num_players: 3,
+            draft: DraftRule::Snake,
+            ..Rules::default()
+        };
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        let mut state = State::with_rules(BoardSpec::new(5, 6).unwrap(), pool, rules).unwrap();
+        let [first, second, third] = [0, 1, 2].map(|index| Seat::new(index).unwrap());
+        check!(
+            draft_order(&mut state)
+                == [first, second, third, third, second, first]
+                    .into_iter()
+                    .cycle()
+                    .take(12)
+                    .collect::<Vec<_>>()
+        );
+        check!(state
+            .inventory()
+            .seats()
+            .all(|seat| state.inventory().hexos_of(seat).len() == 4));
+        // The last player to pick places first.
+        check!(state.current_seat() == Some(third));
+        check!(state.current_player() == None);
+    }
+
+    #[test]
+    fn seats_serialize_like_players() {
+        for player in [Player::First, Player::Second] {
+            check!(
+                serde_json::to_string(&Seat::from(player)).unwrap()
+                    == serde_json::to_string(&player).unwrap()
+            );
+        }
+        let_assert!(Ok(seat) = serde_json::from_str::<Seat>("\"Fourth\""));
+        check!(seat.index() == 3);
+        check!(seat.player() == None);
+        check!(seat.next(4) == Seat::from(Player::First));
+        let moved_hexo = Hexo::new(0).apply(Transform::I).move_to(Pos::new(0, 0));
+        check!(moved_hexo.placed_by(seat).player() == None);
+        check!(moved_hexo.placed_by(Player::Second).player() == Some(Player::Second));
+    }
+
+    #[test]
+    fn four_player_game_ends_when_one_player_is_left() {
+        let rules = Rules {
+            num_players: 4,
+            ..Rules::default()
+        };
+        // The players pick the O, the L, the I and the single tile in turn.
+        let [first, second, third, fourth] = [0, 1, 2, 3].map(|index| Seat::new(index).unwrap());
+        let mut state = small_game(rules, &[0, 1, 2, 3]);
+        check!(state.current_seat() == Some(fourth));
+        place(&mut state, 3, Pos::new(0, 0));
+        place(&mut state, 0, Pos::new(1, 0));
+        // Neither the L nor the I fits anymore and the fourth player has no hexo left, so the
+        // first player is the last one left.
+        check!(state.phase() == GamePhase::End);
+        check!(state.eliminated() == [second, third, fourth]);
+        check!(state.winning_seat() == Some(first));
+        check!(state.winner() == Some(Player::First));
+        check!(state.undo().is_some());
+        check!(state.eliminated().is_empty());
+        check!(state.current_player() == Some(Player::First));
+    }
+
+    #[test]
+    fn unsupported_player_counts_are_rejected() {
+        let pool = HexoSet::all(PieceSet::pentominoes());
+        let spec = BoardSpec::new(5, 6).unwrap();
+        for num_players in [0, 1, 5] {
+            let rules = Rules {
+                num_players,
+                ..Rules::default()
+            };
+            check!(
+                State::with_rules(spec, pool.clone(), rules)
+                    == Err(Error::UnsupportedPlayerCount(num_players))
+            );
+        }
+        let rules = Rules {
+            num_players: 3,
+            draft: DraftRule::Blind,
+            ..Rules::default()
+        };
+        check!(State::with_rules(spec, pool, rules) == Err(Error::UnsupportedPlayerCount(3)));
+    }
 }
@@ -0,0 +1,90 @@
+use super::{
+    bitboard::Bitboard,
+    hexo::{Hexo, HexoSet},
+    state::{GamePhase, Seat},
+};
+use once_cell::sync::Lazy;
+
+/// The number of symmetries of the board, including the identity.
+pub(crate) const N_BOARD_SYMMETRIES: usize = 4;
+
+/// Random keys for Zobrist hashing.
+///
+/// The keys are generated from a fixed seed, so the hashes are stable across runs and can be
+/// stored.
+struct ZobristKeys {
+    tiles: [u128; Bitboard::CAPACITY],
+    remaining_hexos: [u128; HexoSet::CAPACITY],
+    player_hexos: [[u128; HexoSet::CAPACITY]; Seat::MAX],
+    phases: [u128; 3],
+    players: [u128; Seat::MAX],
+    pending_picks: [u128; HexoSet::CAPACITY],
+    eliminated_players: [u128; Seat::MAX],
+}
+
+/// The SplitMix64 generator, which is good enough for generating keys.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (self.next_u64() as u128) << 64 | self.next_u64() as u128
+    }
+}
+
+static KEYS: Lazy<ZobristKeys> = Lazy::new(|| {
+    let mut rng = SplitMix64(0x6865786f6d696e6f);
+    let mut keys = ZobristKeys {
+        tiles: [0; Bitboard::CAPACITY],
+        remaining_hexos: [0; HexoSet::CAPACITY],
+        player_hexos: [[0; HexoSet::CAPACITY]; Seat::MAX],
+        phases: [0; 3],
+        players: [0; Seat::MAX],
+        pending_picks: [0; HexoSet::CAPACITY],
+        eliminated_players: [0; Seat::MAX],
+    };
+    for key in keys
+        .tiles
+        .iter_mut()
+        .chain(keys.remaining_hexos.iter_mut())
+        .chain(keys.player_hexos.iter_mut().flatten())
+        .chain(keys.phases.iter_mut())
+        .chain(keys.players.iter_mut())
+        .chain(keys.pending_picks.iter_mut())
+        .chain(keys.eliminated_players.iter_mut())
+    {
+        *key = rng.next_u128();
+    }
+    keys
+});
+
+pub(crate) fn tile_key(idx: usize) -> u128 {
+    KEYS.tiles[idx]
+}
+
+pub(crate) fn remaining_hexo_key(hexo: Hexo) -> u128 {
+    KEYS.remaining_hexos[hexo.id()]
+}
+
+pub(crate) fn player_hexo_key(seat: Seat, hexo: Hexo) -> u128 {
+    KEYS.player_hexos[seat.index()][hexo.id()]
+}
+
+pub(crate) fn pending_pick_key(hexo: Hexo) -> u128 {
+    KEYS.pending_picks[hexo.id()]
+}
+
+pub(crate) fn eliminated_player_key(seat: Seat) -> u128 {
+    KEYS.eliminated_players[seat.index()]
+}
+
+pub(crate) fn turn_key(phase: GamePhase, seat: Seat) -> u128 {
+    KEYS.phases[phase as usize] ^ KEYS.players[seat.index()]
+}
@@ -62,7 +62,6 @@ struct MatchState {
     game_idx: i32,
     game: GameState,
     first_user_player: Player,
-    prev_actions: Vec<Action>,
     prev_end_state: Option<GameEndState>,
     deadline: Deadline,
 }
@@ -116,11 +115,12 @@ impl MatchActor {
     }
 
     fn broadcast_last_action(&self) {
-        if let Some(action) = self.state.prev_actions.last().cloned() {
+        let num_actions = self.state.game.actions().len();
+        if let Some(action) = self.state.game.actions().next_back() {
             for users in &self.users {
                 users.do_send(WsResponse::MatchEvent(MatchEvent::UserPlay(UserPlay {
                     action,
-                    idx: (self.state.prev_actions.len() - 1) as u32,
+                    idx: (num_actions - 1) as u32,
                 })));
             }
         }
@@ -233,7 +233,7 @@ impl MatchActor {
         {
             history.add_game(
                 state.first_user_player,
-                state.prev_actions.clone(),
+                state.game.actions().collect(),
                 player,
                 reason,
             );
@@ -332,7 +332,7 @@ impl Handler<SyncMatch> for MatchActor {
                     .ok_or(MatchError::Unknown)?;
                 MatchInnerState::Playing(api::GameState::GamePlaying(GameInnerState {
                     you,
-                    prev_actions: self.state.prev_actions.clone(),
+                    prev_actions: self.state.game.actions().collect(),
                 }))
             }
             MatchPhase::GameEnded => {
@@ -352,7 +352,7 @@ impl Handler<SyncMatch> for MatchActor {
                 MatchInnerState::Playing(api::GameState::GameEnded {
                     game_state: GameInnerState {
                         you,
-                        prev_actions: self.state.prev_actions.clone(),
+                        prev_actions: self.state.game.actions().collect(),
                     },
                     end_state: api::GameEndState {
                         winner: info.winner,
@@ -420,7 +420,6 @@ impl Handler<StartNewGame> for MatchActor {
         } else {
             Player::Second
         };
-        state.prev_actions = vec![];
         state.prev_end_state = None;
 
         self.broadcast_new_game();
@@ -507,18 +506,16 @@ impl Handler<PlayerTimeout> for MatchActor {
         }
         match game.phase() {
             GamePhase::Pick => {
-                let hexo = if let Some(hexo) = game
-                    .inventory()
-                    .remaining_hexos()
-                    .iter()
+                let action = if let Some(action) = game
+                    .legal_actions()
                     .next()
                     .crash_match_if_none("there is no remaining hexos in pick phase", ctx)
                 {
-                    hexo
+                    action
                 } else {
                     return;
                 };
-                let _ = self.user_play(msg.player, Action::Pick(hexo), ctx);
+                let _ = self.user_play(msg.player, action, ctx);
             }
             GamePhase::Place => {
                 self.player_win_game(msg.player.other(), GameEndReason::TimeLimitExceed, ctx);
@@ -568,7 +565,6 @@ impl MatchState {
             game_idx: -1,
             game: GameState::new(),
             first_user_player: Player::First,
-            prev_actions: vec![],
             prev_end_state: None,
             deadline: Deadline::new(),
         }
@@ -578,7 +574,6 @@ impl MatchState {
         self.game
             .play(player, action)
             .map_err(|err| MatchError::GameActionError(format!("{err}")))?;
-        self.prev_actions.push(action);
         Ok(())
     }
 
@@ -12,6 +12,8 @@ pub enum MatchError {
     StateNotSynced,
     #[error("game error: {0}")]
     GameError(#[from] hexomino_core::Error),
+    #[error("cannot replay the game: {0}")]
+    ReplayError(#[from] hexomino_core::ReplayError),
 }
 
 type Result<T> = std::result::Result<T, MatchError>;
@@ -54,18 +56,18 @@ pub struct GameState {
 }
 
 impl MatchState {
-    pub fn from_api(match_state: api::MatchState) -> Self {
+    pub fn from_api(match_state: api::MatchState) -> Result<Self> {
         let inner_state = match match_state.state {
             api::MatchInnerState::NotStarted => MatchInnerState::NotStarted,
             api::MatchInnerState::Playing(game_state) => match game_state {
                 api::GameState::GamePlaying(api_state) => MatchInnerState::Playing(Rc::new(
-                    RefCell::new(GameState::new_from_api(api_state)),
+                    RefCell::new(GameState::new_from_api(api_state)?),
                 )),
                 api::GameState::GameEnded {
                     game_state: api_state,
                     end_state,
                 } => {
-                    let mut game_state = GameState::new_from_api(api_state);
+                    let mut game_state = GameState::new_from_api(api_state)?;
                     game_state.end_state = Some(end_state);
                     MatchInnerState::Playing(Rc::new(RefCell::new(game_state)))
                 }
@@ -73,12 +75,12 @@ impl MatchState {
             api::MatchInnerState::Ended { winner } => MatchInnerState::Ended { winner },
         };
 
-        MatchState {
+        Ok(MatchState {
             info: match_state.info,
             game_idx: match_state.game_idx,
             scores: match_state.scores,
             state: inner_state,
-        }
+        })
     }
 
     pub fn phase(&self) -> MatchPhase {
@@ -173,13 +175,13 @@ impl GameState {
             end_state: None,
         }
     }
-    fn new_from_api(state: api::GameInnerState) -> Self {
-        let mut game = GameState::new(state.you);
-        game.num_action = state.prev_actions.len();
-        for action in state.prev_actions {
-            let _ = game.core.current_player_play(action);
-        }
-        game
+    fn new_from_api(state: api::GameInnerState) -> Result<Self> {
+        Ok(Self {
+            core: hexomino_core::State::from_actions(&state.prev_actions)?,
+            me: state.you,
+            num_action: state.prev_actions.len(),
+            end_state: None,
+        })
     }
 
     pub fn current_player_play(
@@ -2,9 +2,8 @@ use std::{cell::RefCell, rc::Rc};
 
 use api::GameEndState;
 use gloo::timers::future::TimeoutFuture;
+use hexomino_ai::Difficulty;
 use hexomino_core::{Action, GamePhase, Player};
-use itertools::Itertools;
-use rand::prelude::SliceRandom;
 use wasm_bindgen_futures::spawn_local;
 use yew::{html, Component, Context, Html};
 
@@ -173,31 +172,7 @@ fn calculate_ai_action(game_state: &GameState) -> Option<Action> {
     {
         return None;
     }
-    let core_state = game_state.core();
-    match core_state.phase() {
-        GamePhase::Pick => {
-            let remaining_hexos = core_state
-                .inventory()
-                .remaining_hexos()
-                .iter()
-                .collect_vec();
-            let chose_hexo = *remaining_hexos.choose(&mut rand::thread_rng()).unwrap();
-            Some(Action::Pick(chose_hexo))
-        }
-        GamePhase::Place => {
-            let mut ai_hexos = core_state
-                .inventory()
-                .hexos_of(game_state.me().other())
-                .iter()
-                .collect_vec();
-            ai_hexos.shuffle(&mut rand::thread_rng());
-            for hexo in ai_hexos {
-                if let Some(placed_hexo) = core_state.board().try_find_placement(hexo) {
-                    return Some(Action::Place(placed_hexo));
-                }
-            }
-            unreachable!("There must be somewhere to place, or else the game should end");
-        }
-        _ => None,
-    }
+    Difficulty::default()
+        .searcher(rand::random())
+        .search(game_state.core())
 }
@@ -69,6 +69,7 @@ impl BoardCanvas {
     fn get_moved_hexo_on_click(&self, board: &Board) -> Option<MovedHexo> {
         let rhexo = self.render_state.rhexo?;
         let pos = BoardRenderer::get_click_pos(
+            board.spec(),
             self.render_state.width as f64,
             self.render_state.height as f64,
             self.render_state.mouse_point?,
@@ -1,7 +1,4 @@
-use hexomino_core::{
-    constants::{COLS, ROWS},
-    Board, Hexo, PlacedHexo, Player, Pos, RHexo,
-};
+use hexomino_core::{BoardSpec, Hexo, PlacedHexo, Player, Pos, RHexo};
 use piet::{
     kurbo::{Affine, Line, Point, Rect, Vec2},
     Color, RenderContext,
@@ -13,6 +10,7 @@ use crate::game::SharedGameState;
 pub struct BoardRenderer<'a> {
     ctx: &'a mut WebRenderContext<'static>,
     config: RenderConfig,
+    spec: BoardSpec,
 }
 
 pub struct RenderConfig {
@@ -37,6 +35,7 @@ const ME_BLOCK_LAST_COLOR: Color = Color::rgb8(48, 240, 32);
 const THEY_BLOCK_LAST_COLOR: Color = Color::rgb8(240, 48, 32);
 const INVALID_BLOCK_COLOR: Color = Color::rgb8(240, 240, 64);
 const DEFAULT_BLOCK_COLOR: Color = Color::GRAY;
+const BLOCKED_TILE_COLOR: Color = Color::grey8(90);
 const BORDER_COLOR: Color = Color::grey8(30);
 const BACKGROUND_COLOR: Color = Color::grey8(240);
 
@@ -71,15 +70,15 @@ enum MousePos {
 }
 
 impl MousePos {
-    fn from_point(point: Point) -> Self {
+    fn from_point(spec: BoardSpec, point: Point) -> Self {
         use MousePos::*;
         let Point { x, y } = point;
         let i = (x / BLOCK_LENGTH).floor() as i32;
         let j = (y / BLOCK_LENGTH).floor() as i32;
-        if i < 0 || i >= COLS as i32 || j < 0 || j >= ROWS as i32 {
-            Free(point)
-        } else {
+        if spec.in_bound(Pos::new(i, j)) {
             Locked(Pos::new(i, j))
+        } else {
+            Free(point)
         }
     }
 
@@ -94,7 +93,8 @@ impl MousePos {
 
 impl<'a> BoardRenderer<'a> {
     pub fn new(ctx: &'a mut WebRenderContext<'static>, config: RenderConfig) -> Self {
-        Self { ctx, config }
+        let spec = config.game_view_state.borrow().core().board().spec();
+        Self { ctx, config, spec }
     }
 
     pub fn clear(&mut self) {
@@ -103,7 +103,7 @@ impl<'a> BoardRenderer<'a> {
 
     pub fn render(&mut self) {
         self.clear();
-        let transform = Self::base_transform(self.config.width, self.config.height);
+        let transform = Self::base_transform(self.spec, self.config.width, self.config.height);
         self.with_affine(transform, |this| {
             this.render_board_tiles();
             this.render_hexos_on_board();
@@ -112,43 +112,69 @@ impl<'a> BoardRenderer<'a> {
         self.ctx.finish().expect("render failed");
     }
 
-    pub fn get_click_pos(width: f64, height: f64, mouse_point: Point) -> Option<Pos> {
+    pub fn get_click_pos(
+        spec: BoardSpec,
+        width: f64,
+        height: f64,
+        mouse_point: Point,
+    ) -> Option<Pos> {
         let transformed_mouse_point =
-            BoardRenderer::base_transform(width, height).inverse() * mouse_point;
-        match MousePos::from_point(transformed_mouse_point) {
+            BoardRenderer::base_transform(spec, width, height).inverse() * mouse_point;
+        match MousePos::from_point(spec, transformed_mouse_point) {
             MousePos::Locked(pos) => Some(pos),
             MousePos::Free(..) => None,
         }
     }
 
     fn render_board_tiles(&mut self) {
-        // Render COLS x ROWS
+        let (rows, cols) = (self.spec.rows(), self.spec.cols());
         let border_brush = self.ctx.solid_brush(BORDER_COLOR);
         let fill_brush = self.ctx.solid_brush(Color::WHITE);
         self.ctx.fill(
             Rect::new(
                 0.0,
                 0.0,
-                BLOCK_LENGTH * COLS as f64,
-                BLOCK_LENGTH * ROWS as f64,
+                BLOCK_LENGTH * cols as f64,
+                BLOCK_LENGTH * rows as f64,
             ),
             &fill_brush,
         );
-        for i in 0..=COLS {
+        let blocked_tiles = self
+            .config
+            .game_view_state
+            .borrow()
+            .core()
+            .board()
+            .layout()
+            .blocked_tiles()
+            .collect::<Vec<_>>();
+        let blocked_brush = self.ctx.solid_brush(BLOCKED_TILE_COLOR);
+        for Pos { x, y } in blocked_tiles {
+            self.ctx.fill(
+                Rect::new(
+                    BLOCK_LENGTH * x as f64,
+                    BLOCK_LENGTH * y as f64,
+                    BLOCK_LENGTH * (x + 1) as f64,
+                    BLOCK_LENGTH * (y + 1) as f64,
+                ),
+                &blocked_brush,
+            );
+        }
+        for i in 0..=cols {
             self.ctx.stroke(
                 Line::new(
                     Point::new(BLOCK_LENGTH * (i as f64), 0.0),
-                    Point::new(BLOCK_LENGTH * (i as f64), BLOCK_LENGTH * (ROWS as f64)),
+                    Point::new(BLOCK_LENGTH * (i as f64), BLOCK_LENGTH * (rows as f64)),
                 ),
                 &border_brush,
                 BOARD_BORDER_WIDTH,
             )
         }
-        for i in 0..=ROWS {
+        for i in 0..=rows {
             self.ctx.stroke(
                 Line::new(
                     Point::new(0.0, BLOCK_LENGTH * (i as f64)),
-                    Point::new(BLOCK_LENGTH * (COLS as f64), BLOCK_LENGTH * (i as f64)),
+                    Point::new(BLOCK_LENGTH * (cols as f64), BLOCK_LENGTH * (i as f64)),
                 ),
                 &border_brush,
                 BOARD_BORDER_WIDTH,
@@ -174,7 +200,7 @@ impl<'a> BoardRenderer<'a> {
     fn render_mouse(&mut self, transform: Affine) {
         let Some(mouse_point) = self.config.mouse_point else { return };
         let Some(rhexo) = self.config.rhexo else { return };
-        let mouse_pos = MousePos::from_point(transform.inverse() * mouse_point);
+        let mouse_pos = MousePos::from_point(self.spec, transform.inverse() * mouse_point);
         let real_point = mouse_pos.to_render_point();
         match mouse_pos {
             MousePos::Locked(pos) => {
@@ -191,14 +217,15 @@ impl<'a> BoardRenderer<'a> {
         }
     }
 
-    fn base_transform(width: f64, height: f64) -> Affine {
-        let block_len = ((width - CANVAS_MARGIN * 2.0) / COLS as f64)
-            .min((height - CANVAS_MARGIN * 2.0) / ROWS as f64)
+    fn base_transform(spec: BoardSpec, width: f64, height: f64) -> Affine {
+        let (rows, cols) = (spec.rows() as f64, spec.cols() as f64);
+        let block_len = ((width - CANVAS_MARGIN * 2.0) / cols)
+            .min((height - CANVAS_MARGIN * 2.0) / rows)
             .max(2.0);
         let scale = Affine::scale(block_len / BLOCK_LENGTH);
 
-        let x_margin = (width - block_len * COLS as f64) / 2.0;
-        let y_margin = (height - block_len * ROWS as f64) / 2.0;
+        let x_margin = (width - block_len * cols) / 2.0;
+        let y_margin = (height - block_len * rows) / 2.0;
         let translate = Affine::translate((x_margin, y_margin));
 
         translate * scale
@@ -210,11 +237,11 @@ impl<'a> BoardRenderer<'a> {
         for tile in moved_hexo.tiles() {
             let x = tile.x as f64 * BLOCK_LENGTH;
             let y = tile.y as f64 * BLOCK_LENGTH;
-            let player = placed_hexos.player();
+            let mine = placed_hexos.player() == Some(me);
             let fill_color = if is_last {
-                color_of_last_hexo(me == player)
+                color_of_last_hexo(mine)
             } else {
-                color_of_hexo(me == player)
+                color_of_hexo(mine)
             };
             let fill = self.ctx.solid_brush(fill_color);
             self.render_block(Point::new(x, y), &fill);
@@ -232,10 +259,11 @@ impl<'a> BoardRenderer<'a> {
         let game_view_state = game_view_state.borrow();
         let board = game_view_state.core().board();
         let moved_hexo = placed_hexos.moved_hexo();
+        let conflicts = board.placement_conflicts(moved_hexo);
         for tile in moved_hexo.tiles() {
             let x = tile.x as f64 * BLOCK_LENGTH;
             let y = tile.y as f64 * BLOCK_LENGTH;
-            let color = if !Board::in_bound(tile) || board.is_placed(tile) {
+            let color = if conflicts.contains(tile) {
                 INVALID_BLOCK_COLOR
             } else {
                 color_of_last_hexo(true)
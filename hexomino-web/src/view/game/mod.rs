@@ -141,7 +141,7 @@ impl Component for GameView {
 impl GameView {
     fn on_sync_match(&mut self, mtch: api::MatchState) -> bool {
         self.deadline = mtch.deadline;
-        self.mtch = Some(MatchState::from_api(mtch));
+        self.mtch = MatchState::from_api(mtch).log_err().ok();
         true
     }
 
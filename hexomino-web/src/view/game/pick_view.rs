@@ -40,7 +40,10 @@ impl Component for PickView {
                     .to_string()
                 })
         };
-        let styled_hexos = Hexo::all_hexos()
+        let styled_hexos = state
+            .core()
+            .piece_set()
+            .hexos()
             .map(|hexo| (hexo, hexo_style_func(hexo)))
             .collect::<Vec<_>>();
         html! {